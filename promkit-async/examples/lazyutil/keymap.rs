@@ -17,8 +17,8 @@ pub fn default(
     for event in event_buffer {
         match event {
             EventBundle::KeyBuffer(chars) => match state.edit_mode {
-                text_editor::Mode::Insert => state.texteditor.insert_chars(&chars),
-                text_editor::Mode::Overwrite => state.texteditor.overwrite_chars(&chars),
+                text_editor::Mode::Insert => state.texteditor.insert_chars(chars),
+                text_editor::Mode::Overwrite => state.texteditor.overwrite_chars(chars),
             },
             EventBundle::HorizontalCursorBuffer(left, right) => {
                 state.texteditor.shift(*left, *right);
@@ -0,0 +1,15 @@
+use promkit::preset::text_area::TextArea;
+
+fn main() -> anyhow::Result<()> {
+    let mut p = TextArea::default()
+        .title("Describe the change (Ctrl+D to submit)")
+        .line_numbers()
+        .text_editor_lines(10)
+        .validator(
+            |text| !text.trim().is_empty(),
+            |_| String::from("The description must not be empty"),
+        )
+        .prompt()?;
+    println!("result: {:?}", p.run()?);
+    Ok(())
+}
@@ -0,0 +1,311 @@
+//! Conversion of strings containing ANSI escape sequences into `StyledGraphemes`,
+//! e.g. the colored output of `git` or `ls --color`.
+//!
+//! SGR sequences (`ESC [ ... m`) are turned into the `ContentStyle` of the following graphemes,
+//! OSC 8 sequences into hyperlinks,
+//! and any other escape sequence is dropped so that it does not affect widths.
+//! Text is interpreted this way where it is requested explicitly,
+//! e.g. with [`text::State::ansi`](crate::text::State::ansi) or `ansi_items` on list-based presets.
+//!
+//! ```
+//! use promkit::{ansi, crossterm::style::ContentStyle};
+//!
+//! let styled = ansi::parse("\x1b[1;31mmodified:\x1b[0m src/lib.rs", ContentStyle::default());
+//! assert_eq!("modified: src/lib.rs", styled.to_string());
+//! ```
+
+use std::sync::Arc;
+
+use crate::{
+    crossterm::style::{Attribute, Color, ContentStyle},
+    grapheme::{StyledGrapheme, StyledGraphemes},
+};
+
+/// Parses `text` into `StyledGraphemes`,
+/// using `base` as the style before any SGR sequence and after a reset.
+pub fn parse<S: AsRef<str>>(text: S, base: ContentStyle) -> StyledGraphemes {
+    let mut ret = StyledGraphemes::default();
+    let mut style = base;
+    let mut link: Option<Arc<str>> = None;
+    let mut chars = text.as_ref().chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            let mut styled = StyledGrapheme::new(ch, style);
+            styled.apply_link(link.clone());
+            ret.push_joined(styled);
+            continue;
+        }
+        match chars.next() {
+            // Control Sequence Introducer: parameters followed by a final byte.
+            Some('[') => {
+                let mut params = String::new();
+                for ch in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&ch) {
+                        if ch == 'm' {
+                            style = apply_sgr(style, base, &params);
+                        }
+                        break;
+                    }
+                    params.push(ch);
+                }
+            }
+            // Operating System Command, terminated by BEL or ST (`ESC \`).
+            Some(']') => {
+                let mut command = String::new();
+                while let Some(ch) = chars.next() {
+                    if ch == '\x07' {
+                        break;
+                    }
+                    if ch == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                    command.push(ch);
+                }
+                // OSC 8 hyperlink: `8;params;url`, where an empty url ends the link.
+                if let Some(rest) = command.strip_prefix("8;") {
+                    link = rest
+                        .split_once(';')
+                        .map(|(_, url)| url)
+                        .filter(|url| !url.is_empty())
+                        .map(Arc::from);
+                }
+            }
+            _ => (),
+        }
+    }
+    ret
+}
+
+/// Applies the SGR parameters `params` to `style`.
+/// Parameters are separated by `;` and may have subparameters separated by `:`,
+/// e.g. `38:2::255:128:0` or `4:3`. Parameters that cannot be parsed are ignored.
+fn apply_sgr(mut style: ContentStyle, base: ContentStyle, params: &str) -> ContentStyle {
+    let mut params = params.split(';');
+
+    while let Some(param) = params.next() {
+        let mut subparams = param.split(':');
+        // An empty parameter, e.g. in `ESC [ m`, means a reset.
+        let code = match subparams.next().unwrap_or_default() {
+            "" => 0,
+            code => match code.parse::<u8>() {
+                Ok(code) => code,
+                Err(_) => continue,
+            },
+        };
+        let subparams = subparams.collect::<Vec<_>>();
+
+        match code {
+            0 => style = base,
+            1 => style.attributes.set(Attribute::Bold),
+            2 => style.attributes.set(Attribute::Dim),
+            3 => style.attributes.set(Attribute::Italic),
+            4 => match subparams.first().map(|kind| kind.parse::<u8>()) {
+                None => style.attributes.set(Attribute::Underlined),
+                Some(Ok(0)) => unset_underlines(&mut style),
+                Some(Ok(kind)) => {
+                    if let Some(underline) = underline(kind) {
+                        unset_underlines(&mut style);
+                        style.attributes.set(underline);
+                    }
+                }
+                Some(Err(_)) => (),
+            },
+            5 => style.attributes.set(Attribute::SlowBlink),
+            7 => style.attributes.set(Attribute::Reverse),
+            8 => style.attributes.set(Attribute::Hidden),
+            9 => style.attributes.set(Attribute::CrossedOut),
+            22 => {
+                style.attributes.unset(Attribute::Bold);
+                style.attributes.unset(Attribute::Dim);
+            }
+            23 => style.attributes.unset(Attribute::Italic),
+            24 => unset_underlines(&mut style),
+            25 => style.attributes.unset(Attribute::SlowBlink),
+            27 => style.attributes.unset(Attribute::Reverse),
+            28 => style.attributes.unset(Attribute::Hidden),
+            29 => style.attributes.unset(Attribute::CrossedOut),
+            30..=37 => style.foreground_color = Some(standard_color(code - 30)),
+            38 => {
+                if let Some(color) = extended_color(&subparams, &mut params) {
+                    style.foreground_color = Some(color);
+                }
+            }
+            39 => style.foreground_color = base.foreground_color,
+            40..=47 => style.background_color = Some(standard_color(code - 40)),
+            48 => {
+                if let Some(color) = extended_color(&subparams, &mut params) {
+                    style.background_color = Some(color);
+                }
+            }
+            49 => style.background_color = base.background_color,
+            90..=97 => style.foreground_color = Some(bright_color(code - 90)),
+            100..=107 => style.background_color = Some(bright_color(code - 100)),
+            _ => (),
+        }
+    }
+    style
+}
+
+/// Parses the arguments of `38` and `48`, i.e. `5;n` or `2;r;g;b`,
+/// given either as `subparams` (`5:n`, `2:r:g:b` or `2::r:g:b` with a color space)
+/// or as the following parameters.
+fn extended_color<'a, I: Iterator<Item = &'a str>>(
+    subparams: &[&str],
+    params: &mut I,
+) -> Option<Color> {
+    let byte = |s: &str| s.parse::<u8>().ok();
+    match subparams {
+        // The arguments are consumed even if they are malformed,
+        // so that they are not taken for other parameters.
+        [] => match byte(params.next()?)? {
+            5 => params.next().and_then(byte).map(Color::AnsiValue),
+            2 => {
+                let (r, g, b) = (params.next(), params.next(), params.next());
+                Some(Color::Rgb {
+                    r: byte(r?)?,
+                    g: byte(g?)?,
+                    b: byte(b?)?,
+                })
+            }
+            _ => None,
+        },
+        ["5", n, ..] => Some(Color::AnsiValue(byte(n)?)),
+        ["2", _, r, g, b, ..] | ["2", r, g, b] => Some(Color::Rgb {
+            r: byte(r)?,
+            g: byte(g)?,
+            b: byte(b)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the attribute of the underline style `kind` given as `4:kind`.
+fn underline(kind: u8) -> Option<Attribute> {
+    match kind {
+        1 => Some(Attribute::Underlined),
+        2 => Some(Attribute::DoubleUnderlined),
+        3 => Some(Attribute::Undercurled),
+        4 => Some(Attribute::Underdotted),
+        5 => Some(Attribute::Underdashed),
+        _ => None,
+    }
+}
+
+fn unset_underlines(style: &mut ContentStyle) {
+    for kind in 1..=5 {
+        if let Some(underline) = underline(kind) {
+            style.attributes.unset(underline);
+        }
+    }
+}
+
+fn standard_color(index: u8) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        _ => Color::Grey,
+    }
+}
+
+fn bright_color(index: u8) -> Color {
+    match index {
+        0 => Color::DarkGrey,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod parse {
+        use crate::{
+            ansi::parse,
+            crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+            grapheme::StyledGraphemes,
+            style::StyleBuilder,
+        };
+
+        #[test]
+        fn test() {
+            let bold_red = StyleBuilder::new()
+                .fgc(Color::DarkRed)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_iter([
+                    StyledGraphemes::from_str("ab", bold_red),
+                    StyledGraphemes::from("c"),
+                ]),
+                parse("\x1b[1;31mab\x1b[0mc", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_with_extended_colors() {
+            let style = StyleBuilder::new()
+                .fgc(Color::AnsiValue(208))
+                .bgc(Color::Rgb { r: 1, g: 2, b: 3 })
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_str("x", style),
+                parse("\x1b[38;5;208;48;2;1;2;3mx", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_with_colon_subparameters() {
+            let style = StyleBuilder::new()
+                .fgc(Color::Rgb { r: 1, g: 2, b: 3 })
+                .bgc(Color::AnsiValue(208))
+                .attrs(Attributes::from(Attribute::Undercurled))
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_str("x", style),
+                parse("\x1b[38:2::1:2:3;48:5:208;4:3mx", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_ignores_malformed_parameters() {
+            let bold = StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_str("x", bold),
+                parse("\x1b[1m\x1b[300;38;2;999;0;0mx", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_drops_other_sequences() {
+            let styled = parse("\x1b[2K\x1b]0;title\x07a\x1b[1Gbc", ContentStyle::default());
+            assert_eq!("abc", styled.to_string());
+            assert_eq!(3, styled.widths());
+        }
+
+        #[test]
+        fn test_with_hyperlink() {
+            let styled = parse(
+                "a\x1b]8;;url\x1b\\b\x1b]8;;\x1b\\c",
+                ContentStyle::default(),
+            );
+            assert_eq!("abc", styled.to_string());
+            assert_eq!(
+                vec![None, Some("url"), None],
+                styled.iter().map(|g| g.link()).collect::<Vec<_>>()
+            );
+        }
+    }
+}
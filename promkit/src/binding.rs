@@ -0,0 +1,1152 @@
+//! Declarative key bindings: named [`Action`]s and tables mapping keys to them.
+//!
+//! The default keymap of each preset looks up the pressed key in its [`Bindings`]
+//! and performs the bound action, so a single key can be remapped per prompt
+//! without rewriting the keymap:
+//!
+//! ```
+//! use promkit::{
+//!     binding::{Action, Chord, KeyPress},
+//!     preset::readline::Readline,
+//! };
+//!
+//! let readline = Readline::default()
+//!     .bind("ctrl-b".parse::<KeyPress>().unwrap(), Action::MoveWordBackward)
+//!     .unbind("alt-b".parse::<KeyPress>().unwrap())
+//!     .bind("ctrl-x ctrl-u".parse::<Chord>().unwrap(), Action::EraseAll);
+//! ```
+//!
+//! Keys are written as specs such as `enter`, `ctrl-w`, `alt-b` or `shift-tab`:
+//! any number of the modifiers `ctrl`, `alt` and `shift` followed by a single character
+//! or one of the key names `enter`, `esc`, `tab`, `backspace`, `delete`, `insert`,
+//! `home`, `end`, `pageup`, `pagedown`, `up`, `down`, `left`, `right`, `space` and `f1` to `f24`.
+//! `+` can be used instead of `-`, e.g. `Ctrl+W`.
+//!
+//! Actions can also be bound to a [`Chord`], a sequence of keys pressed one after another
+//! and written as key specs separated by spaces, e.g. `g g` or `ctrl-x ctrl-e`.
+//! After the first keys of a chord the bindings wait for the rest
+//! (see [`Bindings::pending`]), and start over if the next key does not continue the chord
+//! or is pressed after [`Bindings::chord_timeout`].
+//!
+//! Bindings can also be changed by users with a config file (see [`BindingsConfig`]),
+//! which has a table per preset mapping keys to actions written in snake case,
+//! or to `none` to remove the default binding of the key:
+//!
+//! ```toml
+//! [readline]
+//! ctrl-b = "move_word_backward"
+//! alt-b = "none"
+//!
+//! [listbox]
+//! j = "move_down"
+//! k = "move_up"
+//! "g g" = "move_to_head"
+//! ```
+
+use std::{
+    borrow::Borrow,
+    fmt, fs,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use indexmap::IndexMap;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    keyhint::KeyHint,
+    locale::Messages,
+};
+
+/// The operations that can be bound to keys.
+///
+/// Each preset performs the actions that make sense for it and ignores the others,
+/// e.g. [`Action::Toggle`] checks an item of a checkbox and folds a node of a tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Submits the input or the selected item.
+    Submit,
+    /// Aborts the prompt with an error.
+    Interrupt,
+
+    /// Moves the cursor of the input one character to the left.
+    MoveBackward,
+    /// Moves the cursor of the input one character to the right.
+    MoveForward,
+    /// Moves the cursor to the start of the input, or to the first item.
+    MoveToHead,
+    /// Moves the cursor to the end of the input, or to the last item.
+    MoveToTail,
+    /// Moves the cursor to the start of the previous word.
+    MoveWordBackward,
+    /// Moves the cursor to the start of the next word.
+    MoveWordForward,
+    /// Erases the character before the cursor.
+    EraseBackward,
+    /// Erases the character under the cursor.
+    EraseForward,
+    /// Erases the whole input.
+    EraseAll,
+    /// Erases from the start of the input to the cursor.
+    EraseToHead,
+    /// Erases from the cursor to the end of the input.
+    EraseToTail,
+    /// Erases from the start of the previous word to the cursor.
+    EraseWordBackward,
+    /// Erases from the cursor to the start of the next word.
+    EraseWordForward,
+    /// Inserts the text killed most recently (see [`KillRing`](crate::text_editor::KillRing)).
+    Yank,
+    /// Replaces the text just yanked with the text killed before it.
+    YankPop,
+    /// Reverts the last edit of the input.
+    Undo,
+    /// Reapplies the edit reverted last.
+    Redo,
+    /// Moves the cursor one character to the left, extending the selection.
+    SelectBackward,
+    /// Moves the cursor one character to the right, extending the selection.
+    SelectForward,
+    /// Moves the cursor to the start of the input, extending the selection.
+    SelectToHead,
+    /// Moves the cursor to the end of the input, extending the selection.
+    SelectToTail,
+    /// Copies the selected text (see [`Clipboard`](crate::text_editor::Clipboard)).
+    Copy,
+    /// Copies and erases the selected text.
+    Cut,
+    /// Inserts the copied text, replacing the selection.
+    Paste,
+    /// Inserts a line break.
+    InsertNewline,
+    /// Moves the cursor to the previous line of the input.
+    MoveLineUp,
+    /// Moves the cursor to the next line of the input.
+    MoveLineDown,
+    /// Recalls the previous entry from the history.
+    HistoryPrevious,
+    /// Recalls the next entry from the history.
+    HistoryNext,
+    /// Completes the input with the suggestions.
+    Complete,
+    /// Selects the next suggestion.
+    SuggestNext,
+    /// Selects the previous suggestion.
+    SuggestPrevious,
+
+    /// Moves to the previous item, node or field.
+    MoveUp,
+    /// Moves to the next item, node or field.
+    MoveDown,
+    /// Checks or unchecks an item, or folds or unfolds a node.
+    Toggle,
+}
+
+impl Action {
+    /// All actions with their names in config files.
+    const NAMES: [(&'static str, Action); 37] = [
+        ("submit", Action::Submit),
+        ("interrupt", Action::Interrupt),
+        ("move_backward", Action::MoveBackward),
+        ("move_forward", Action::MoveForward),
+        ("move_to_head", Action::MoveToHead),
+        ("move_to_tail", Action::MoveToTail),
+        ("move_word_backward", Action::MoveWordBackward),
+        ("move_word_forward", Action::MoveWordForward),
+        ("erase_backward", Action::EraseBackward),
+        ("erase_forward", Action::EraseForward),
+        ("erase_all", Action::EraseAll),
+        ("erase_to_head", Action::EraseToHead),
+        ("erase_to_tail", Action::EraseToTail),
+        ("erase_word_backward", Action::EraseWordBackward),
+        ("erase_word_forward", Action::EraseWordForward),
+        ("yank", Action::Yank),
+        ("yank_pop", Action::YankPop),
+        ("undo", Action::Undo),
+        ("redo", Action::Redo),
+        ("select_backward", Action::SelectBackward),
+        ("select_forward", Action::SelectForward),
+        ("select_to_head", Action::SelectToHead),
+        ("select_to_tail", Action::SelectToTail),
+        ("copy", Action::Copy),
+        ("cut", Action::Cut),
+        ("paste", Action::Paste),
+        ("insert_newline", Action::InsertNewline),
+        ("move_line_up", Action::MoveLineUp),
+        ("move_line_down", Action::MoveLineDown),
+        ("history_previous", Action::HistoryPrevious),
+        ("history_next", Action::HistoryNext),
+        ("complete", Action::Complete),
+        ("suggest_next", Action::SuggestNext),
+        ("suggest_previous", Action::SuggestPrevious),
+        ("move_up", Action::MoveUp),
+        ("move_down", Action::MoveDown),
+        ("toggle", Action::Toggle),
+    ];
+
+    /// Returns whether the action moves the cursor of a text editor or erases text.
+    pub fn edits_text(&self) -> bool {
+        matches!(
+            self,
+            Action::MoveBackward
+                | Action::MoveForward
+                | Action::MoveToHead
+                | Action::MoveToTail
+                | Action::MoveWordBackward
+                | Action::MoveWordForward
+                | Action::EraseBackward
+                | Action::EraseForward
+                | Action::EraseAll
+                | Action::EraseToHead
+                | Action::EraseToTail
+                | Action::EraseWordBackward
+                | Action::EraseWordForward
+                | Action::Yank
+                | Action::YankPop
+        )
+    }
+
+    /// Returns whether the action erases text onto the kill ring,
+    /// i.e. erases more than a single character.
+    pub fn kills(&self) -> bool {
+        matches!(
+            self,
+            Action::EraseAll
+                | Action::EraseToHead
+                | Action::EraseToTail
+                | Action::EraseWordBackward
+                | Action::EraseWordForward
+        )
+    }
+
+    /// Returns the name of the action in config files, e.g. `erase_word_backward`.
+    pub fn name(&self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|(_, action)| action == self)
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An error returned when an action name is unknown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseActionError(String);
+
+impl fmt::Display for ParseActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = Action::NAMES
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "unknown action `{}`, expected `none` or one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseActionError {}
+
+impl FromStr for Action {
+    type Err = ParseActionError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::NAMES
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, action)| *action)
+            .ok_or_else(|| ParseActionError(name.to_string()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// A key together with its modifiers, e.g. <kbd>Ctrl</kbd> + <kbd>W</kbd>.
+///
+/// Shifted characters are represented by the character itself without [`KeyModifiers::SHIFT`]
+/// (e.g. `shift-a` is `A`), and <kbd>Shift</kbd> + <kbd>Tab</kbd> by [`KeyCode::BackTab`],
+/// as terminals report them inconsistently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers;
+        let code = match code {
+            KeyCode::Char(ch) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(ch.to_uppercase().next().unwrap_or(ch))
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// A key without modifiers.
+    pub fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// A character with <kbd>Ctrl</kbd>.
+    pub fn ctrl(ch: char) -> Self {
+        Self::new(KeyCode::Char(ch), KeyModifiers::CONTROL)
+    }
+
+    /// A character with <kbd>Alt</kbd>.
+    pub fn alt(ch: char) -> Self {
+        Self::new(KeyCode::Char(ch), KeyModifiers::ALT)
+    }
+
+    /// A key with <kbd>Shift</kbd>, e.g. <kbd>Shift + ←</kbd>.
+    pub fn shift(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::SHIFT)
+    }
+}
+
+impl From<&KeyEvent> for KeyPress {
+    fn from(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl From<KeyPress> for KeyEvent {
+    fn from(key: KeyPress) -> Self {
+        KeyEvent::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyPress {
+    /// Formats the key as shown in key hints, e.g. `Ctrl+W`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(ch) if !self.modifiers.is_empty() => {
+                write!(f, "{}", ch.to_uppercase())
+            }
+            KeyCode::Char(ch) => write!(f, "{}", ch),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// An error returned when a key spec cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseKeyError(String);
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid key `{}`, expected modifiers such as `ctrl-` or `alt-` \
+             followed by a character or a key name such as `enter` or `up`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl FromStr for KeyPress {
+    type Err = ParseKeyError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let err = || ParseKeyError(spec.to_string());
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec.trim();
+        'modifiers: loop {
+            for (name, modifier) in [
+                ("ctrl", KeyModifiers::CONTROL),
+                ("control", KeyModifiers::CONTROL),
+                ("alt", KeyModifiers::ALT),
+                ("meta", KeyModifiers::ALT),
+                ("shift", KeyModifiers::SHIFT),
+            ] {
+                let Some(prefix) = rest.get(..name.len()) else {
+                    continue;
+                };
+                let after = &rest[name.len()..];
+                if prefix.eq_ignore_ascii_case(name)
+                    && (after.starts_with('-') || after.starts_with('+'))
+                    && after.len() > 1
+                {
+                    modifiers |= modifier;
+                    rest = &after[1..];
+                    continue 'modifiers;
+                }
+            }
+            break;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            (None, _) => return Err(err()),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n @ 1..=24)) => KeyCode::F(n),
+                    _ => return Err(err()),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyPress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// A non-empty sequence of keys pressed one after another, e.g. `g g` or `Ctrl+X Ctrl+E`.
+///
+/// A single key is a chord of length one, so anything that accepts a chord
+/// also accepts a [`KeyPress`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Chord(Vec<KeyPress>);
+
+impl Chord {
+    /// Creates a chord from `keys`, or returns `None` if there are no keys.
+    pub fn new<I: IntoIterator<Item = KeyPress>>(keys: I) -> Option<Self> {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        (!keys.is_empty()).then_some(Self(keys))
+    }
+
+    /// Returns the keys of the chord in the order they are pressed.
+    pub fn keys(&self) -> &[KeyPress] {
+        &self.0
+    }
+}
+
+impl From<KeyPress> for Chord {
+    fn from(key: KeyPress) -> Self {
+        Self(vec![key])
+    }
+}
+
+impl Borrow<[KeyPress]> for Chord {
+    fn borrow(&self) -> &[KeyPress] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Chord {
+    /// Formats the keys as shown in key hints separated by spaces, e.g. `Ctrl+X Ctrl+E`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseKeyError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let keys = spec
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<KeyPress>, _>>()?;
+        Self::new(keys).ok_or_else(|| ParseKeyError(spec.to_string()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Chord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// How long [`Bindings`] wait for the next key of a chord by default.
+pub const DEFAULT_CHORD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A table from keys and chords to the actions they perform,
+/// together with the keys of a chord pressed so far.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bindings {
+    table: IndexMap<Chord, Action>,
+    /// How long to wait for the next key of a chord.
+    timeout: Duration,
+    /// Keys of a chord pressed so far, and when the last of them was pressed.
+    pending: Vec<KeyPress>,
+    pending_since: Option<Instant>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            table: Default::default(),
+            timeout: DEFAULT_CHORD_TIMEOUT,
+            pending: Default::default(),
+            pending_since: Default::default(),
+        }
+    }
+}
+
+impl<K: Into<Chord>> FromIterator<(K, Action)> for Bindings {
+    fn from_iter<I: IntoIterator<Item = (K, Action)>>(iter: I) -> Self {
+        Self {
+            table: iter
+                .into_iter()
+                .map(|(key, action)| (key.into(), action))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<K: Into<Chord>> Extend<(K, Action)> for Bindings {
+    /// Binds each key to its action, replacing the actions previously bound to them.
+    fn extend<I: IntoIterator<Item = (K, Action)>>(&mut self, iter: I) {
+        for (key, action) in iter {
+            self.bind(key, action);
+        }
+    }
+}
+
+impl IntoIterator for Bindings {
+    type Item = (Chord, Action);
+    type IntoIter = indexmap::map::IntoIter<Chord, Action>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.table.into_iter()
+    }
+}
+
+impl Bindings {
+    /// Binds `key` (a single key or a chord) to `action`,
+    /// returning the action previously bound to it.
+    ///
+    /// A key bound on its own is performed immediately,
+    /// even if it is also the first key of a chord.
+    pub fn bind<K: Into<Chord>>(&mut self, key: K, action: Action) -> Option<Action> {
+        self.table.insert(key.into(), action)
+    }
+
+    /// Removes the binding of `key`, returning the action that was bound to it.
+    pub fn unbind<K: Into<Chord>>(&mut self, key: K) -> Option<Action> {
+        self.table.shift_remove(&key.into())
+    }
+
+    /// Returns the action bound to `key`.
+    pub fn get<K: Into<Chord>>(&self, key: K) -> Option<Action> {
+        self.table.get(&key.into()).copied()
+    }
+
+    /// Returns how long to wait for the next key of a chord.
+    pub fn chord_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets how long to wait for the next key of a chord.
+    pub fn set_chord_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the keys of a chord pressed so far, if the bindings are waiting for the rest.
+    /// The keys are dropped once [`Self::chord_timeout`] has passed since the last of them.
+    pub fn pending(&self) -> Option<Chord> {
+        if self.expired() {
+            return None;
+        }
+        Chord::new(self.pending.iter().copied())
+    }
+
+    /// Returns whether the bindings are waiting for the rest of a chord.
+    /// Keymaps use this to tell a key consumed as part of a chord
+    /// from a key without any binding.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty() && !self.expired()
+    }
+
+    /// Returns how long the bindings keep waiting for the rest of a chord,
+    /// so that prompts can hide the keys pressed so far when it times out.
+    pub fn pending_timeout(&self) -> Option<Duration> {
+        if !self.is_pending() {
+            return None;
+        }
+        self.pending_since
+            .map(|since| self.timeout.saturating_sub(since.elapsed()))
+    }
+
+    /// Returns whether the chord pressed so far has timed out.
+    fn expired(&self) -> bool {
+        self.pending_since
+            .is_some_and(|since| since.elapsed() > self.timeout)
+    }
+
+    /// Returns the action bound to the key pressed in `event`,
+    /// taking the keys of a chord pressed before into account.
+    ///
+    /// Returns `None` while a chord is incomplete. If the key does not continue the chord,
+    /// the keys pressed before are dropped and the key is looked up on its own.
+    pub fn lookup(&mut self, event: &Event) -> Option<Action> {
+        let key = pressed_key(event)?;
+        if self.expired() {
+            self.pending.clear();
+        }
+        self.pending.push(key);
+        if self.pending.len() > 1
+            && !self.table.contains_key(self.pending.as_slice())
+            && !self.is_prefix(&self.pending)
+        {
+            self.pending = vec![key];
+        }
+
+        let action = self.table.get(self.pending.as_slice()).copied();
+        if action.is_none() && self.is_prefix(&self.pending) {
+            self.pending_since = Some(Instant::now());
+        } else {
+            self.pending.clear();
+            self.pending_since = None;
+        }
+        action
+    }
+
+    /// Returns whether `keys` are the first keys of a longer chord.
+    fn is_prefix(&self, keys: &[KeyPress]) -> bool {
+        self.table
+            .keys()
+            .any(|chord| chord.0.len() > keys.len() && chord.0.starts_with(keys))
+    }
+
+    /// Returns the keys and chords bound to `action`, in the order they were bound.
+    pub fn keys(&self, action: Action) -> Vec<Chord> {
+        self.iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Returns all bindings in the order they were bound.
+    pub fn iter(&self) -> impl Iterator<Item = (&Chord, Action)> + '_ {
+        self.table.iter().map(|(key, action)| (key, *action))
+    }
+
+    /// Returns key hints for the bindings of the actions accepted by `filter`,
+    /// in the order they were bound and described with [`Messages::action`].
+    /// Keys of actions with the same description are shown together, e.g. `↑/↓ move`.
+    pub fn hints<F: Fn(Action) -> bool>(&self, messages: &Messages, filter: F) -> Vec<KeyHint> {
+        let mut hints: Vec<KeyHint> = vec![];
+        for (key, action) in self.iter().filter(|(_, action)| filter(*action)) {
+            let description = messages.action(action);
+            match hints
+                .iter_mut()
+                .find(|hint| hint.description == description)
+            {
+                Some(hint) => hint.key = format!("{}/{}", hint.key, key),
+                None => hints.push(KeyHint::new(key.to_string(), description)),
+            }
+        }
+        hints
+    }
+
+    /// Applies `overrides` on top of these bindings.
+    pub fn merge(&mut self, overrides: &BindingOverrides) {
+        for (key, action) in overrides.iter() {
+            match action {
+                Some(action) => self.bind(key.clone(), action),
+                None => self.unbind(key.clone()),
+            };
+        }
+    }
+}
+
+/// Key bindings for editing text shared by the presets with a text editor,
+/// which extend them with their own keys.
+///
+/// | Key                         | Action
+/// | :-------------------------- | :-------------------------------------------
+/// | <kbd>←</kbd>, <kbd>→</kbd>  | [`Action::MoveBackward`], [`Action::MoveForward`]
+/// | <kbd>Ctrl + A</kbd>, <kbd>Ctrl + E</kbd> | [`Action::MoveToHead`], [`Action::MoveToTail`]
+/// | <kbd>Alt + B</kbd>, <kbd>Alt + F</kbd>   | [`Action::MoveWordBackward`], [`Action::MoveWordForward`]
+/// | <kbd>Backspace</kbd>        | [`Action::EraseBackward`]
+/// | <kbd>Ctrl + W</kbd>, <kbd>Alt + D</kbd>  | [`Action::EraseWordBackward`], [`Action::EraseWordForward`]
+/// | <kbd>Ctrl + U</kbd>         | [`Action::EraseAll`]
+/// | <kbd>Ctrl + Y</kbd>, <kbd>Alt + Y</kbd>  | [`Action::Yank`], [`Action::YankPop`]
+/// | <kbd>Ctrl + Z</kbd>, <kbd>Ctrl + _</kbd> | [`Action::Undo`]
+/// | <kbd>Alt + Z</kbd>          | [`Action::Redo`]
+/// | <kbd>Shift + ←/→</kbd>      | [`Action::SelectBackward`], [`Action::SelectForward`]
+/// | <kbd>Shift + Home/End</kbd> | [`Action::SelectToHead`], [`Action::SelectToTail`]
+/// | <kbd>Alt + C/X/V</kbd>      | [`Action::Copy`], [`Action::Cut`], [`Action::Paste`]
+pub fn text_editing() -> Bindings {
+    Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Left), Action::MoveBackward),
+        (KeyPress::plain(KeyCode::Right), Action::MoveForward),
+        (KeyPress::ctrl('a'), Action::MoveToHead),
+        (KeyPress::ctrl('e'), Action::MoveToTail),
+        (KeyPress::alt('b'), Action::MoveWordBackward),
+        (KeyPress::alt('f'), Action::MoveWordForward),
+        (KeyPress::plain(KeyCode::Backspace), Action::EraseBackward),
+        (KeyPress::ctrl('w'), Action::EraseWordBackward),
+        (KeyPress::alt('d'), Action::EraseWordForward),
+        (KeyPress::ctrl('u'), Action::EraseAll),
+        (KeyPress::ctrl('y'), Action::Yank),
+        (KeyPress::alt('y'), Action::YankPop),
+        (KeyPress::ctrl('z'), Action::Undo),
+        (KeyPress::ctrl('_'), Action::Undo),
+        // Terminals send Ctrl+_ as the same byte as Ctrl+7.
+        (KeyPress::ctrl('7'), Action::Undo),
+        (KeyPress::alt('z'), Action::Redo),
+        (KeyPress::shift(KeyCode::Left), Action::SelectBackward),
+        (KeyPress::shift(KeyCode::Right), Action::SelectForward),
+        (KeyPress::shift(KeyCode::Home), Action::SelectToHead),
+        (KeyPress::shift(KeyCode::End), Action::SelectToTail),
+        (KeyPress::alt('c'), Action::Copy),
+        (KeyPress::alt('x'), Action::Cut),
+        (KeyPress::alt('v'), Action::Paste),
+    ])
+}
+
+/// Changes to the bindings of a preset, read from a config file:
+/// each key or chord is bound to an action, or unbound with `None` (`"none"` in the file).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindingOverrides(IndexMap<Chord, Option<Action>>);
+
+impl<K: Into<Chord>> FromIterator<(K, Option<Action>)> for BindingOverrides {
+    fn from_iter<I: IntoIterator<Item = (K, Option<Action>)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, action)| (key.into(), action))
+                .collect(),
+        )
+    }
+}
+
+impl BindingOverrides {
+    /// Returns the changes in the order they were written.
+    pub fn iter(&self) -> impl Iterator<Item = (&Chord, Option<Action>)> + '_ {
+        self.0.iter().map(|(key, action)| (key, *action))
+    }
+}
+
+impl<'de> Deserialize<'de> for BindingOverrides {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IndexMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, action)| {
+                let key = key.parse::<Chord>().map_err(de::Error::custom)?;
+                let action =
+                    match action.as_str() {
+                        "none" => None,
+                        name => Some(name.parse::<Action>().map_err(|e| {
+                            de::Error::custom(format!("{} (bound to `{}`)", e, key))
+                        })?),
+                    };
+                Ok((key, action))
+            })
+            .collect()
+    }
+}
+
+/// Key bindings configured by users for each preset, e.g. loaded from a `bindings.toml`.
+///
+/// Each table is merged over the default bindings of the preset
+/// with the `merge_bindings` method of the preset,
+/// and `readline_suggest` over the bindings of the readline while suggestions are shown
+/// with [`Readline::merge_suggest_bindings`](crate::preset::readline::Readline::merge_suggest_bindings):
+///
+/// ```
+/// use promkit::{binding::BindingsConfig, preset::listbox::Listbox};
+///
+/// let config = BindingsConfig::from_toml(
+///     r#"
+///     [listbox]
+///     j = "move_down"
+///     k = "move_up"
+///     "#,
+/// )
+/// .unwrap();
+/// let listbox = Listbox::new(["a", "b"]).merge_bindings(&config.listbox);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BindingsConfig {
+    pub readline: BindingOverrides,
+    pub readline_suggest: BindingOverrides,
+    pub listbox: BindingOverrides,
+    pub query_selector: BindingOverrides,
+    pub checkbox: BindingOverrides,
+    pub tree: BindingOverrides,
+    pub json: BindingOverrides,
+    pub form: BindingOverrides,
+    pub text_area: BindingOverrides,
+}
+
+impl BindingsConfig {
+    /// Parses bindings written in TOML.
+    pub fn from_toml(s: &str) -> anyhow::Result<Self> {
+        toml::from_str(s).context("failed to parse key bindings as TOML")
+    }
+
+    /// Parses bindings written in JSON.
+    pub fn from_json(s: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(s).context("failed to parse key bindings as JSON")
+    }
+
+    /// Loads bindings from a file,
+    /// which is read as JSON if its extension is `.json` and as TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read key bindings from {}", path.display()))?;
+        let config = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        };
+        config.with_context(|| format!("invalid key bindings in {}", path.display()))
+    }
+}
+
+/// Returns the key pressed in `event`, ignoring key releases and other events.
+pub fn pressed_key(event: &Event) -> Option<KeyPress> {
+    match event {
+        Event::Key(
+            key @ KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            },
+        ) => Some(KeyPress::from(key)),
+        _ => None,
+    }
+}
+
+/// Returns the character typed in `event`, if it is a key press
+/// of a character without modifiers other than <kbd>Shift</kbd>.
+pub fn input_char(event: &Event) -> Option<char> {
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) if (*modifiers - KeyModifiers::SHIFT).is_empty() => Some(*ch),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod parse {
+        use crate::{
+            binding::KeyPress,
+            crossterm::event::{KeyCode, KeyModifiers},
+        };
+
+        #[test]
+        fn test() {
+            assert_eq!(Ok(KeyPress::ctrl('w')), "ctrl-w".parse());
+            assert_eq!(Ok(KeyPress::alt('b')), "Alt+b".parse());
+            assert_eq!(Ok(KeyPress::plain(KeyCode::Enter)), "enter".parse());
+            assert_eq!(Ok(KeyPress::plain(KeyCode::Char(' '))), "space".parse());
+            assert_eq!(Ok(KeyPress::plain(KeyCode::F(5))), "f5".parse());
+            assert_eq!(Ok(KeyPress::plain(KeyCode::Char('f'))), "f".parse());
+            assert_eq!(Ok(KeyPress::ctrl('-')), "ctrl--".parse());
+            assert_eq!(
+                Ok(KeyPress::new(
+                    KeyCode::Up,
+                    KeyModifiers::CONTROL | KeyModifiers::ALT
+                )),
+                "ctrl-alt-up".parse()
+            );
+        }
+
+        #[test]
+        fn test_with_shift() {
+            assert_eq!(Ok(KeyPress::plain(KeyCode::Char('A'))), "shift-a".parse());
+            assert_eq!(Ok(KeyPress::plain(KeyCode::BackTab)), "shift-tab".parse());
+            assert_eq!(
+                KeyPress::plain(KeyCode::Char('?')),
+                KeyPress::new(KeyCode::Char('?'), KeyModifiers::SHIFT)
+            );
+        }
+
+        #[test]
+        fn test_with_invalid_specs() {
+            assert!("".parse::<KeyPress>().is_err());
+            assert!("ctrl-".parse::<KeyPress>().is_err());
+            assert!("hyper-w".parse::<KeyPress>().is_err());
+            assert!("f25".parse::<KeyPress>().is_err());
+        }
+    }
+
+    mod display {
+        use crate::{
+            binding::{Chord, KeyPress},
+            crossterm::event::KeyCode,
+        };
+
+        #[test]
+        fn test() {
+            assert_eq!("Ctrl+W", KeyPress::ctrl('w').to_string());
+            assert_eq!("Enter", KeyPress::plain(KeyCode::Enter).to_string());
+            assert_eq!("↑", KeyPress::plain(KeyCode::Up).to_string());
+            assert_eq!("Space", KeyPress::plain(KeyCode::Char(' ')).to_string());
+        }
+
+        #[test]
+        fn test_with_chords() {
+            assert_eq!(
+                "Ctrl+X Ctrl+E",
+                "ctrl-x ctrl-e".parse::<Chord>().unwrap().to_string()
+            );
+            assert!("  ".parse::<Chord>().is_err());
+        }
+    }
+
+    mod lookup {
+        use std::time::Duration;
+
+        use crate::{
+            binding::{Action, Bindings, Chord, KeyPress},
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        };
+
+        #[test]
+        fn test() {
+            let mut bindings = Bindings::from_iter([
+                (KeyPress::ctrl('w'), Action::EraseWordBackward),
+                (KeyPress::plain(KeyCode::Enter), Action::Submit),
+            ]);
+            let ctrl_w = Event::Key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+            assert_eq!(Some(Action::EraseWordBackward), bindings.lookup(&ctrl_w));
+
+            let release = Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('w'),
+                KeyModifiers::CONTROL,
+                KeyEventKind::Release,
+            ));
+            assert_eq!(None, bindings.lookup(&release));
+
+            bindings.unbind(KeyPress::ctrl('w'));
+            assert_eq!(None, bindings.lookup(&ctrl_w));
+            assert_eq!(
+                vec![Chord::from(KeyPress::plain(KeyCode::Enter))],
+                bindings.keys(Action::Submit)
+            );
+        }
+
+        fn press(spec: &str) -> Event {
+            Event::Key(KeyEvent::from(spec.parse::<KeyPress>().unwrap()))
+        }
+
+        #[test]
+        fn test_with_chords() {
+            let mut bindings = Bindings::from_iter([
+                ("g g".parse::<Chord>().unwrap(), Action::MoveToHead),
+                ("ctrl-x ctrl-e".parse().unwrap(), Action::Submit),
+                ("j".parse().unwrap(), Action::MoveDown),
+            ]);
+            assert_eq!(None, bindings.lookup(&press("g")));
+            assert_eq!(Some("g".parse().unwrap()), bindings.pending());
+            assert_eq!(Some(Action::MoveToHead), bindings.lookup(&press("g")));
+            assert!(!bindings.is_pending());
+
+            assert_eq!(None, bindings.lookup(&press("ctrl-x")));
+            assert_eq!(Some(Action::Submit), bindings.lookup(&press("ctrl-e")));
+
+            // A key that does not continue the chord is looked up on its own.
+            assert_eq!(None, bindings.lookup(&press("g")));
+            assert_eq!(Some(Action::MoveDown), bindings.lookup(&press("j")));
+            assert!(!bindings.is_pending());
+        }
+
+        #[test]
+        fn test_with_timeout() {
+            let mut bindings =
+                Bindings::from_iter([("g g".parse::<Chord>().unwrap(), Action::MoveToHead)]);
+            bindings.set_chord_timeout(Duration::from_millis(10));
+            assert_eq!(None, bindings.lookup(&press("g")));
+            assert!(bindings.pending_timeout().is_some());
+            std::thread::sleep(Duration::from_millis(20));
+            // The keys pressed before the timeout are no longer shown as pending.
+            assert_eq!(None, bindings.pending());
+            assert_eq!(None, bindings.pending_timeout());
+            assert_eq!(None, bindings.lookup(&press("g")));
+            assert!(bindings.is_pending());
+        }
+    }
+
+    mod hints {
+        use crate::{
+            binding::{Action, BindingOverrides, Bindings, KeyPress},
+            crossterm::event::KeyCode,
+            keyhint::KeyHint,
+            locale::Messages,
+        };
+
+        #[test]
+        fn test() {
+            let mut bindings = Bindings::from_iter([
+                (KeyPress::plain(KeyCode::Enter), Action::Submit),
+                (KeyPress::plain(KeyCode::Up), Action::MoveUp),
+                (KeyPress::plain(KeyCode::Down), Action::MoveDown),
+                (KeyPress::ctrl('c'), Action::Interrupt),
+            ]);
+            let messages = Messages::default();
+            assert_eq!(
+                vec![
+                    KeyHint::new("Enter", "submit"),
+                    KeyHint::new("↑/↓", "move"),
+                    KeyHint::new("Ctrl+C", "quit"),
+                ],
+                bindings.hints(&messages, |_| true)
+            );
+
+            bindings.merge(&BindingOverrides::from_iter([
+                (KeyPress::plain(KeyCode::Enter), None),
+                (KeyPress::ctrl('d'), Some(Action::Submit)),
+                (KeyPress::plain(KeyCode::Char('j')), Some(Action::MoveDown)),
+            ]));
+            assert_eq!(
+                vec![
+                    KeyHint::new("↑/↓/j", "move"),
+                    KeyHint::new("Ctrl+C", "quit"),
+                    KeyHint::new("Ctrl+D", "submit"),
+                ],
+                bindings.hints(&messages, |_| true)
+            );
+            assert_eq!(
+                vec![KeyHint::new("Ctrl+D", "submit")],
+                bindings.hints(&messages, |action| action == Action::Submit)
+            );
+        }
+    }
+
+    mod from_toml {
+        use crate::{
+            binding::{Action, BindingsConfig, Chord, KeyPress},
+            crossterm::event::KeyCode,
+            preset::readline::keymap::default_bindings,
+        };
+
+        #[test]
+        fn test() {
+            let config = BindingsConfig::from_toml(
+                r#"
+                [readline]
+                ctrl-b = "move_word_backward"
+                alt-b = "none"
+                "ctrl-x ctrl-u" = "erase_all"
+                "#,
+            )
+            .unwrap();
+            let mut bindings = default_bindings();
+            bindings.merge(&config.readline);
+            assert_eq!(
+                Some(Action::MoveWordBackward),
+                bindings.get(KeyPress::ctrl('b'))
+            );
+            assert_eq!(None, bindings.get(KeyPress::alt('b')));
+            assert_eq!(
+                Some(Action::Submit),
+                bindings.get(KeyPress::plain(KeyCode::Enter))
+            );
+            assert_eq!(
+                Some(Action::EraseAll),
+                bindings.get("ctrl-x ctrl-u".parse::<Chord>().unwrap())
+            );
+        }
+
+        #[test]
+        fn test_with_unknown_action() {
+            let err = BindingsConfig::from_toml(
+                r#"
+                [listbox]
+                j = "move_donw"
+                "#,
+            )
+            .unwrap_err();
+            let message = format!("{:#}", err);
+            assert!(message.contains("unknown action `move_donw`"));
+            assert!(message.contains("move_down"));
+        }
+
+        #[test]
+        fn test_with_invalid_key() {
+            let err = BindingsConfig::from_toml(
+                r#"
+                [listbox]
+                hyper-j = "move_down"
+                "#,
+            )
+            .unwrap_err();
+            assert!(format!("{:#}", err).contains("invalid key `hyper-j`"));
+        }
+
+        #[test]
+        fn test_with_unknown_preset() {
+            assert!(BindingsConfig::from_toml("[readlin]").is_err());
+        }
+    }
+}
@@ -1,7 +1,9 @@
 pub mod checkbox;
 mod cursor;
 pub use cursor::Cursor;
+pub mod help;
 pub mod json;
+pub mod keyhint;
 pub mod listbox;
 pub mod snapshot;
 pub mod text;
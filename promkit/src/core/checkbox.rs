@@ -71,6 +71,11 @@ impl Checkbox {
         self.listbox.items()
     }
 
+    /// Returns a mutable reference to the vector of items in the listbox.
+    pub fn items_mut(&mut self) -> &mut Vec<StyledGraphemes> {
+        self.listbox.items_mut()
+    }
+
     /// Returns the current position of the cursor within the listbox.
     pub fn position(&self) -> usize {
         self.listbox.position()
@@ -23,8 +23,8 @@ pub struct State {
 
     /// Style for the selected line.
     pub active_item_style: ContentStyle,
-    /// Style for unselected lines.
-    pub inactive_item_style: ContentStyle,
+    /// Style for unselected lines, which keep the styles of their items if `None`.
+    pub inactive_item_style: Option<ContentStyle>,
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
@@ -58,14 +58,18 @@ impl PaneFactory for State {
                     StyledGraphemes::from_iter([&StyledGraphemes::from(&self.cursor), &f(i), item])
                         .apply_style(self.active_item_style)
                 } else {
-                    StyledGraphemes::from_iter([
+                    let init = StyledGraphemes::from_iter([
                         &StyledGraphemes::from(
                             " ".repeat(StyledGraphemes::from(&self.cursor).widths()),
                         ),
                         &f(i),
                         item,
-                    ])
-                    .apply_style(self.inactive_item_style)
+                    ]);
+                    if let Some(style) = &self.inactive_item_style {
+                        init.apply_style(*style)
+                    } else {
+                        init
+                    }
                 }
             })
             .fold((vec![], 0), |(mut acc, pos), item| {
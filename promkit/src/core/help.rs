@@ -0,0 +1,264 @@
+use crate::{
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind},
+        style::ContentStyle,
+    },
+    grapheme::StyledGraphemes,
+    keyhint::KeyHint,
+    locale,
+    pane::Pane,
+    theme::Theme,
+    PaneFactory,
+};
+
+/// Represents the state of a help overlay
+/// that lists every binding of the currently active keymap.
+///
+/// The overlay is hidden until `toggle_key` is pressed,
+/// can be scrolled with the arrow keys or the mouse wheel,
+/// and is closed again with <kbd>Esc</kbd>, `toggle_key` or any other key.
+#[derive(Clone)]
+pub struct State {
+    /// Hints to be listed.
+    pub hints: Vec<KeyHint>,
+
+    /// Title displayed on the first row of the overlay.
+    pub title: String,
+    /// Key to open (and close) the overlay.
+    pub toggle_key: KeyEvent,
+
+    /// Whether the overlay is currently shown.
+    pub visible: bool,
+    /// Index of the first hint displayed, used for scrolling.
+    pub position: usize,
+
+    /// Style for the title.
+    pub title_style: ContentStyle,
+    /// Style for the key column.
+    pub key_style: ContentStyle,
+    /// Style for the description column.
+    pub description_style: ContentStyle,
+
+    /// Number of lines available for rendering.
+    pub lines: Option<usize>,
+}
+
+impl State {
+    /// Creates a hidden overlay without hints, styled by `theme` and opened with `toggle_key`.
+    ///
+    /// Presets editing text open it with <kbd>F1</kbd>, leaving <kbd>?</kbd> to be typed,
+    /// and the others with <kbd>?</kbd>.
+    pub fn new(theme: &Theme, toggle_key: KeyEvent) -> Self {
+        Self {
+            hints: Default::default(),
+            title: locale::messages().help_title,
+            toggle_key,
+            visible: false,
+            position: 0,
+            title_style: theme.title_style,
+            key_style: theme.hint_key_style,
+            description_style: theme.hint_description_style,
+            lines: Default::default(),
+        }
+    }
+
+    fn is_toggle_key(&self, key: &KeyEvent) -> bool {
+        // Terminals report SHIFT for shifted characters such as `?`
+        // inconsistently, so it is ignored when comparing characters.
+        let ignore = match self.toggle_key.code {
+            KeyCode::Char(_) => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        key.kind == KeyEventKind::Press
+            && key.code == self.toggle_key.code
+            && key.modifiers.difference(ignore) == self.toggle_key.modifiers.difference(ignore)
+    }
+
+    /// Handles an event for the overlay.
+    ///
+    /// Returns `true` if the event was consumed by the overlay,
+    /// in which case it must not be passed to the keymap.
+    /// While the overlay is shown, it consumes the keys closing and scrolling it,
+    /// and any other key closes it and is passed on, so that e.g. typing is not lost.
+    pub fn evaluate(&mut self, event: &Event) -> bool {
+        if !self.visible {
+            if let Event::Key(key) = event {
+                if self.is_toggle_key(key) {
+                    self.visible = true;
+                    self.position = 0;
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        match event {
+            Event::Key(key) if key.code == KeyCode::Esc || self.is_toggle_key(key) => {
+                self.visible = false;
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::NONE,
+                ..
+            })
+            | Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                ..
+            }) => {
+                self.position = self.position.saturating_sub(1);
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::NONE,
+                ..
+            })
+            | Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                ..
+            }) => {
+                if self.position + 1 < self.hints.len() {
+                    self.position += 1;
+                }
+            }
+            Event::Key(_) => {
+                self.visible = false;
+                return false;
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        if !self.visible {
+            return Pane::new(vec![], 0);
+        }
+
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+
+        let key_width = self
+            .hints
+            .iter()
+            .map(|hint| StyledGraphemes::from(&hint.key).widths())
+            .max()
+            .unwrap_or(0);
+
+        let mut matrix = StyledGraphemes::from_str(&self.title, self.title_style)
+            .matrixify(width as usize, height, 0)
+            .0;
+
+        for hint in self.hints.iter().skip(self.position) {
+            if matrix.len() >= height {
+                break;
+            }
+            let padding = key_width - StyledGraphemes::from(&hint.key).widths();
+            let row = StyledGraphemes::from_iter([
+                StyledGraphemes::from_str(&hint.key, self.key_style),
+                StyledGraphemes::from(" ".repeat(padding + 2)),
+                StyledGraphemes::from_str(&hint.description, self.description_style),
+            ]);
+            matrix.extend(row.matrixify(width as usize, height, 0).0);
+        }
+        matrix.truncate(height);
+
+        Pane::new(matrix, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_state() -> State {
+        State {
+            hints: vec![
+                KeyHint::new("Enter", "submit"),
+                KeyHint::new("Ctrl+W", "erase word"),
+                KeyHint::new("↑/↓", "move"),
+            ],
+            title: String::from("Key bindings"),
+            toggle_key: KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+            visible: false,
+            position: 0,
+            title_style: ContentStyle::default(),
+            key_style: ContentStyle::default(),
+            description_style: ContentStyle::default(),
+            lines: None,
+        }
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn test_toggle() {
+            let mut state = new_state();
+            assert!(!state.evaluate(&key(KeyCode::Char('a'), KeyModifiers::NONE)));
+            assert!(state.evaluate(&key(KeyCode::Char('?'), KeyModifiers::SHIFT)));
+            assert!(state.visible);
+            assert!(state.evaluate(&key(KeyCode::Esc, KeyModifiers::NONE)));
+            assert!(!state.visible);
+        }
+
+        #[test]
+        fn test_other_keys_pass_through() {
+            let mut state = new_state();
+            state.visible = true;
+            assert!(!state.evaluate(&key(KeyCode::Char('a'), KeyModifiers::NONE)));
+            assert!(!state.visible);
+        }
+
+        #[test]
+        fn test_scroll() {
+            let mut state = new_state();
+            state.visible = true;
+            state.evaluate(&key(KeyCode::Down, KeyModifiers::NONE));
+            state.evaluate(&key(KeyCode::Down, KeyModifiers::NONE));
+            state.evaluate(&key(KeyCode::Down, KeyModifiers::NONE));
+            assert_eq!(2, state.position);
+            state.evaluate(&key(KeyCode::Up, KeyModifiers::NONE));
+            assert_eq!(1, state.position);
+        }
+
+        #[test]
+        fn test_interrupt_passes_through() {
+            let mut state = new_state();
+            state.visible = true;
+            assert!(!state.evaluate(&key(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        }
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test_hidden() {
+            assert!(new_state().create_pane(100, 10).is_empty());
+        }
+
+        #[test]
+        fn test() {
+            let mut state = new_state();
+            state.visible = true;
+            state.position = 1;
+            assert_eq!(
+                vec!["Key bindings", "Ctrl+W  erase word", "↑/↓     move"],
+                state
+                    .create_pane(100, 10)
+                    .extract(10)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
@@ -126,7 +126,7 @@ impl JsonNode {
                     .collect();
                 JsonNode::Object {
                     children,
-                    children_visible: depth.map_or(true, |d| d > 0),
+                    children_visible: depth.is_none_or(|d| d > 0),
                 }
             }
             serde_json::Value::Array(vec) => {
@@ -136,7 +136,7 @@ impl JsonNode {
                     .collect();
                 JsonNode::Array {
                     children,
-                    children_visible: depth.map_or(true, |d| d > 0),
+                    children_visible: depth.is_none_or(|d| d > 0),
                 }
             }
             _ => JsonNode::Leaf(value),
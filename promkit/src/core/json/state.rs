@@ -1,6 +1,6 @@
 use crate::{
     crossterm::style::{Attribute, ContentStyle},
-    grapheme::StyledGraphemes,
+    grapheme::{StyledGraphemes, Wrap},
     pane::Pane,
     PaneFactory,
 };
@@ -40,6 +40,9 @@ pub struct State {
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
 
+    /// How each line is laid out when it is wider than the pane.
+    pub wrap: Wrap,
+
     /// The number of spaces used for indentation in the rendered JSON structure.
     /// This value multiplies with the indentation level of a JSON element to determine
     /// the total indentation space. For example, an `indent` value of 4 means each
@@ -201,7 +204,7 @@ impl PaneFactory for State {
                 }
             })
             .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
+                let rows = item.matrixify_with(self.wrap, width as usize, height, 0).0;
                 if pos < self.stream.cursor.cross_contents_position() + height {
                     acc.extend(rows);
                 }
@@ -0,0 +1,23 @@
+mod state;
+pub use state::State;
+
+/// Describes a single key binding for display purposes,
+/// pairing a human-readable key (e.g. `Alt+B`) with a short description
+/// of the action it triggers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyHint {
+    /// Human-readable representation of the key, e.g. `Ctrl+W`.
+    pub key: String,
+    /// Short description of the action bound to the key.
+    pub description: String,
+}
+
+impl KeyHint {
+    /// Creates a new `KeyHint` from a key label and its description.
+    pub fn new<K: AsRef<str>, D: AsRef<str>>(key: K, description: D) -> Self {
+        Self {
+            key: key.as_ref().to_string(),
+            description: description.as_ref().to_string(),
+        }
+    }
+}
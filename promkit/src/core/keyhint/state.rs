@@ -0,0 +1,165 @@
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, theme::Theme,
+    PaneFactory,
+};
+
+use super::KeyHint;
+
+/// Represents the state of a footer that shows key hints
+/// for the currently active keymap.
+///
+/// Hints are packed into rows from left to right,
+/// and a hint is never split across rows unless it is wider than the pane itself.
+#[derive(Clone)]
+pub struct State {
+    /// Hints to be rendered.
+    pub hints: Vec<KeyHint>,
+    /// Keys of a chord pressed so far (e.g. `Ctrl+X`), shown before the hints
+    /// while the rest of the chord is awaited.
+    pub pending: Option<String>,
+
+    /// String placed between two adjacent hints on the same row.
+    pub separator: String,
+
+    /// Style for the key part of each hint.
+    pub key_style: ContentStyle,
+    /// Style for the description part of each hint.
+    pub description_style: ContentStyle,
+
+    /// Number of lines available for rendering.
+    pub lines: Option<usize>,
+}
+
+impl State {
+    /// Creates a footer without hints, styled by `theme` and fitting on a single line.
+    pub fn new(theme: &Theme) -> Self {
+        Self {
+            hints: Default::default(),
+            pending: Default::default(),
+            separator: String::from("  "),
+            key_style: theme.hint_key_style,
+            description_style: theme.hint_description_style,
+            lines: Some(1),
+        }
+    }
+
+    fn styled_hint(&self, hint: &KeyHint) -> StyledGraphemes {
+        StyledGraphemes::from_iter([
+            StyledGraphemes::from_str(&hint.key, self.key_style),
+            StyledGraphemes::from_str(" ", self.description_style),
+            StyledGraphemes::from_str(&hint.description, self.description_style),
+        ])
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+
+        let separator = StyledGraphemes::from_str(&self.separator, self.description_style);
+
+        let pending = self
+            .pending
+            .iter()
+            .map(|keys| StyledGraphemes::from_str(format!("{} …", keys), self.key_style));
+
+        let mut matrix: Vec<StyledGraphemes> = vec![];
+        let mut row = StyledGraphemes::default();
+        for styled in pending.chain(self.hints.iter().map(|hint| self.styled_hint(hint))) {
+            if !row.is_empty()
+                && row.widths() + separator.widths() + styled.widths() <= width as usize
+            {
+                row = StyledGraphemes::from_iter([&row, &separator, &styled]);
+                continue;
+            }
+            if !row.is_empty() {
+                matrix.push(row);
+            }
+            if styled.widths() <= width as usize {
+                row = styled;
+            } else {
+                matrix.extend(styled.matrixify(width as usize, height, 0).0);
+                row = StyledGraphemes::default();
+            }
+        }
+        if !row.is_empty() {
+            matrix.push(row);
+        }
+        matrix.truncate(height);
+
+        Pane::new(matrix, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod create_pane {
+        use crate::{
+            crossterm::style::ContentStyle,
+            keyhint::{KeyHint, State},
+            PaneFactory,
+        };
+
+        fn new_state(lines: Option<usize>) -> State {
+            State {
+                hints: vec![
+                    KeyHint::new("Enter", "submit"),
+                    KeyHint::new("Tab", "complete"),
+                    KeyHint::new("Ctrl+W", "erase word"),
+                ],
+                pending: None,
+                separator: String::from(" | "),
+                key_style: ContentStyle::default(),
+                description_style: ContentStyle::default(),
+                lines,
+            }
+        }
+
+        #[test]
+        fn test_with_enough_width() {
+            let pane = new_state(None).create_pane(100, 10);
+            assert_eq!(
+                vec!["Enter submit | Tab complete | Ctrl+W erase word"],
+                pane.extract(10)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_narrow_width() {
+            let pane = new_state(None).create_pane(30, 10);
+            assert_eq!(
+                vec!["Enter submit | Tab complete", "Ctrl+W erase word"],
+                pane.extract(10)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_pending_chord() {
+            let mut state = new_state(None);
+            state.pending = Some(String::from("Ctrl+X"));
+            let pane = state.create_pane(100, 10);
+            assert_eq!(
+                vec!["Ctrl+X … | Enter submit | Tab complete | Ctrl+W erase word"],
+                pane.extract(10)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_with_limited_lines() {
+            let pane = new_state(Some(1)).create_pane(30, 10);
+            assert_eq!(1, pane.visible_row_count());
+        }
+    }
+}
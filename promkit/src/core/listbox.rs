@@ -48,6 +48,11 @@ impl Listbox {
         self.0.contents()
     }
 
+    /// Returns a mutable reference to the vector of items in the listbox.
+    pub fn items_mut(&mut self) -> &mut Vec<StyledGraphemes> {
+        self.0.contents_mut()
+    }
+
     /// Returns the current position of the cursor within the listbox.
     pub fn position(&self) -> usize {
         self.0.position()
@@ -1,4 +1,9 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGraphemes, Wrap},
+    pane::Pane,
+    PaneFactory,
+};
 
 use super::Listbox;
 
@@ -20,6 +25,9 @@ pub struct State {
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+
+    /// How each item is laid out when it is wider than the pane.
+    pub wrap: Wrap,
 }
 
 impl PaneFactory for State {
@@ -59,7 +67,7 @@ impl PaneFactory for State {
                 }
             })
             .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
+                let rows = item.matrixify_with(self.wrap, width as usize, height, 0).0;
                 if pos < self.listbox.position() + height {
                     acc.extend(rows);
                 }
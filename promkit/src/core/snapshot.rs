@@ -36,7 +36,7 @@ impl<R: PaneFactory + Clone + 'static> Snapshot<R> {
     }
 
     /// Returns a reference to the state of the renderer before any changes were applied (`before`).
-    pub fn borrow_before(&self) -> Ref<R> {
+    pub fn borrow_before(&self) -> Ref<'_, R> {
         self.before.borrow()
     }
 
@@ -1,4 +1,24 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    ansi,
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGraphemes, Wrap},
+    markup,
+    pane::Pane,
+    PaneFactory,
+};
+
+/// How the text of a [`State`] is interpreted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The text is shown as it is.
+    #[default]
+    Plain,
+    /// Markup tags (see [`crate::markup`]) in the text style parts of it.
+    Markup,
+    /// ANSI escape sequences (see [`crate::ansi`]) in the text style parts of it,
+    /// e.g. in the colored output of other tools.
+    Ansi,
+}
 
 /// Represents the state of a text-based component within the application.
 ///
@@ -11,9 +31,41 @@ pub struct State {
 
     /// Style for the text string.
     pub style: ContentStyle,
+
+    /// How the text is laid out when it is wider than the pane.
+    pub wrap: Wrap,
+
+    /// How the text is interpreted, which is as it is by default.
+    pub format: Format,
 }
 
 impl State {
+    /// Creates a state for `text` shown as it is in `style`.
+    pub fn new<T: AsRef<str>>(text: T, style: ContentStyle) -> Self {
+        Self::with_format(text, style, Format::Plain)
+    }
+
+    /// Creates a state for `text` written in markup (see [`crate::markup`]),
+    /// using `style` outside of any tag.
+    pub fn markup<T: AsRef<str>>(text: T, style: ContentStyle) -> Self {
+        Self::with_format(text, style, Format::Markup)
+    }
+
+    /// Creates a state for `text` containing ANSI escape sequences (see [`crate::ansi`]),
+    /// using `style` before any of them.
+    pub fn ansi<T: AsRef<str>>(text: T, style: ContentStyle) -> Self {
+        Self::with_format(text, style, Format::Ansi)
+    }
+
+    fn with_format<T: AsRef<str>>(text: T, style: ContentStyle, format: Format) -> Self {
+        Self {
+            text: text.as_ref().to_string(),
+            style,
+            wrap: Default::default(),
+            format,
+        }
+    }
+
     pub fn replace(&mut self, renderer: Self) {
         *self = renderer;
     }
@@ -21,11 +73,42 @@ impl State {
 
 impl PaneFactory for State {
     fn create_pane(&self, width: u16, height: u16) -> Pane {
-        let (matrix, _) = StyledGraphemes::from_str(&self.text, self.style).matrixify(
-            width as usize,
-            height as usize,
-            0,
-        );
+        let styled = match self.format {
+            Format::Plain => StyledGraphemes::from_str(&self.text, self.style),
+            Format::Markup => markup::parse(&self.text, self.style),
+            Format::Ansi => ansi::parse(&self.text, self.style),
+        };
+        let (matrix, _) = styled.matrixify_with(self.wrap, width as usize, height as usize, 0);
         Pane::new(matrix, 0)
     }
 }
+
+#[cfg(test)]
+mod test {
+    mod create_pane {
+        use crate::{
+            crossterm::style::ContentStyle,
+            text::{Format, State},
+            PaneFactory,
+        };
+
+        fn rows(state: &State) -> Vec<String> {
+            state
+                .create_pane(40, 10)
+                .extract(10)
+                .iter()
+                .map(|row| row.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn test_format() {
+            let mut state = State::new("Continue? [bold][y/N][/]", ContentStyle::default());
+            assert_eq!(vec!["Continue? [bold][y/N][/]"], rows(&state));
+            state.format = Format::Markup;
+            assert_eq!(vec!["Continue? [y/N]"], rows(&state));
+            state = State::ansi("\x1b[1mContinue?", ContentStyle::default());
+            assert_eq!(vec!["Continue?"], rows(&state));
+        }
+    }
+}
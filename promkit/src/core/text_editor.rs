@@ -1,14 +1,23 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::Range};
 
 use crate::{
     core::cursor::Cursor,
     grapheme::{StyledGrapheme, StyledGraphemes},
 };
 
+mod clipboard;
+pub use clipboard::{write_osc52, Clipboard};
+mod highlight;
+pub use highlight::Highlighter;
 mod history;
 pub use history::History;
+mod kill_ring;
+pub use kill_ring::KillRing;
 mod state;
 pub use state::State;
+mod undo;
+use undo::{Edit, UndoStack};
+pub mod vi;
 
 /// Edit mode.
 #[derive(Clone, Default)]
@@ -23,12 +32,21 @@ pub enum Mode {
 /// A text editor that supports basic editing operations
 /// such as insert, delete, and overwrite.
 /// It utilizes a cursor to navigate and manipulate the text.
+/// The text can span multiple lines separated by `'\n'`.
 #[derive(Clone)]
-pub struct TextEditor(Cursor<StyledGraphemes>);
+pub struct TextEditor {
+    cursor: Cursor<StyledGraphemes>,
+    /// Previous states of the text to undo edits.
+    undo: UndoStack,
+    /// The position where the selection starts, which spans from it to the cursor.
+    anchor: Option<usize>,
+    /// The column the cursor keeps while moving up and down across shorter lines.
+    goal_column: Option<usize>,
+}
 
 impl Default for TextEditor {
     fn default() -> Self {
-        Self(Cursor::new(
+        Self::from_cursor(Cursor::new(
             // Set cursor
             StyledGraphemes::from(" "),
             0,
@@ -39,15 +57,24 @@ impl Default for TextEditor {
 
 impl TextEditor {
     pub fn new<S: AsRef<str>>(s: S) -> Self {
-        let mut buf = s.as_ref().to_owned();
-        buf.push(' ');
+        let mut buf = StyledGraphemes::from(s);
+        buf.push_back(StyledGrapheme::from(' '));
         let pos = buf.len() - 1;
-        Self(Cursor::new(StyledGraphemes::from(buf), pos, false))
+        Self::from_cursor(Cursor::new(buf, pos, false))
+    }
+
+    fn from_cursor(cursor: Cursor<StyledGraphemes>) -> Self {
+        Self {
+            cursor,
+            undo: Default::default(),
+            anchor: None,
+            goal_column: None,
+        }
     }
 
     /// Returns the current text including the cursor.
     pub fn text(&self) -> StyledGraphemes {
-        self.0.contents().clone()
+        self.cursor.contents().clone()
     }
 
     /// Returns the text without the cursor.
@@ -59,164 +86,416 @@ impl TextEditor {
 
     /// Returns the current position of the cursor within the text.
     pub fn position(&self) -> usize {
-        self.0.position()
+        self.cursor.position()
+    }
+
+    /// Returns the number of characters (graphemes) in the text, excluding the cursor.
+    pub fn len(&self) -> usize {
+        self.cursor.contents().len() - 1
+    }
+
+    /// Returns `true` if the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     /// Masks all characters except the cursor with the specified mask character.
     pub fn masking(&self, mask: char) -> StyledGraphemes {
-        self.text()
-            .chars()
-            .into_iter()
+        let len = self.cursor.contents().len();
+        self.cursor
+            .contents()
+            .iter()
             .enumerate()
-            .map(|(i, c)| StyledGrapheme::from(if i == self.text().len() - 1 { c } else { mask }))
+            .map(|(i, g)| {
+                if i == len - 1 {
+                    g.clone()
+                } else {
+                    StyledGrapheme::from(mask)
+                }
+            })
             .collect::<StyledGraphemes>()
     }
 
     /// Replaces the current text with new text and positions the cursor at the end.
     pub fn replace(&mut self, new: &str) {
-        let mut buf = new.to_owned();
-        buf.push(' ');
-        let pos = buf.len() - 1;
-        *self = Self(Cursor::new(StyledGraphemes::from(buf), pos, false));
+        self.edited(Edit::Other);
+        self.cursor = Self::new(new).cursor;
     }
 
     /// Inserts a character at the current cursor position.
+    /// A character that extends the grapheme before the cursor
+    /// (e.g. a combining mark or one following a zero width joiner) is joined to it.
+    /// Consecutive inserted characters are undone together (see [`Self::undo`]).
     pub fn insert(&mut self, ch: char) {
-        let pos = self.position();
-        self.0.contents_mut().insert(pos, StyledGrapheme::from(ch));
-        self.forward();
+        self.edited(Edit::Insert);
+        self.insert_char(ch);
     }
 
+    /// Inserts characters at the current cursor position, which are undone together.
     pub fn insert_chars(&mut self, vch: &Vec<char>) {
+        if !vch.is_empty() {
+            self.edited(Edit::Other);
+        }
         for ch in vch {
-            self.insert(*ch);
+            self.insert_char(*ch);
         }
     }
 
+    fn insert_char(&mut self, ch: char) {
+        let pos = self.position();
+        if pos > 0 && self.cursor.contents_mut()[pos - 1].join(&ch.to_string()) {
+            return;
+        }
+        self.cursor
+            .contents_mut()
+            .insert(pos, StyledGrapheme::from(ch));
+        self.cursor.forward();
+    }
+
     /// Overwrites the character at the current cursor position with the specified character.
+    /// Consecutive overwritten characters are undone together like inserted ones.
     pub fn overwrite(&mut self, ch: char) {
-        if self.0.is_tail() {
-            self.insert(ch)
-        } else {
-            let pos = self.position();
-            self.0
-                .contents_mut()
-                .replace_range(pos..pos + 1, &ch.to_string());
-            self.forward();
-        }
+        self.edited(Edit::Insert);
+        self.overwrite_char(ch);
     }
 
+    /// Overwrites characters from the current cursor position, which are undone together.
     pub fn overwrite_chars(&mut self, vch: &Vec<char>) {
+        if !vch.is_empty() {
+            self.edited(Edit::Other);
+        }
         for ch in vch {
-            self.overwrite(*ch);
+            self.overwrite_char(*ch);
+        }
+    }
+
+    fn overwrite_char(&mut self, ch: char) {
+        if self.cursor.is_tail() {
+            self.insert_char(ch)
+        } else {
+            let pos = self.position();
+            self.cursor
+                .contents_mut()
+                .replace_range(pos..pos + 1, ch.to_string());
+            self.cursor.forward();
         }
     }
 
     /// Erases the character before the cursor position.
     pub fn erase(&mut self) {
-        if !self.0.is_head() {
-            self.backward();
+        if !self.cursor.is_head() {
+            self.edited(Edit::Other);
+            self.cursor.backward();
+            let pos = self.position();
+            self.cursor.contents_mut().drain(pos..pos + 1);
+        }
+    }
+
+    /// Erases the character at the cursor position.
+    pub fn erase_forward(&mut self) {
+        if !self.cursor.is_tail() {
+            self.edited(Edit::Other);
             let pos = self.position();
-            self.0.contents_mut().drain(pos..pos + 1);
+            self.cursor.contents_mut().drain(pos..pos + 1);
+        }
+    }
+
+    /// Clears all text and moves the cursor to the start, returning the erased text.
+    pub fn erase_all(&mut self) -> String {
+        let erased = self.text_without_cursor().to_string();
+        if !erased.is_empty() {
+            self.edited(Edit::Other);
+            self.cursor = Self::default().cursor;
         }
+        erased
+    }
+
+    /// Erases the text from the beginning to the cursor position, returning the erased text.
+    pub fn erase_to_head(&mut self) -> String {
+        self.erase_to_position(0)
     }
 
-    /// Clears all text and resets the editor to its default state.
-    pub fn erase_all(&mut self) {
-        *self = Self::default();
+    /// Erases the text from the cursor position to the end, returning the erased text.
+    pub fn erase_to_tail(&mut self) -> String {
+        self.erase_to_position(self.cursor.contents().len() - 1)
     }
 
     /// Erases the text from the current cursor position to the specified position,
     /// considering whether pos is greater or smaller than the current position.
-    fn erase_to_position(&mut self, pos: usize) {
+    fn erase_to_position(&mut self, pos: usize) -> String {
         let current_pos = self.position();
         if pos > current_pos {
-            self.0.contents_mut().drain(current_pos..pos);
+            self.erase_range(current_pos..pos)
         } else {
-            self.0.contents_mut().drain(pos..current_pos);
-            self.0.move_to(pos);
+            self.erase_range(pos..current_pos)
         }
     }
 
+    /// Erases the characters in `range` (clamped to the text)
+    /// and moves the cursor to the start of it, returning the erased text.
+    pub fn erase_range(&mut self, range: Range<usize>) -> String {
+        let start = range.start.min(self.len());
+        let end = range.end.clamp(start, self.len());
+        if start < end {
+            self.edited(Edit::Other);
+        } else {
+            self.moved();
+        }
+        let erased = self
+            .cursor
+            .contents_mut()
+            .drain(start..end)
+            .collect::<StyledGraphemes>();
+        self.cursor.move_to(start);
+        erased.to_string()
+    }
+
     /// Finds the nearest previous index of any character in `word_break_chars` from the cursor position.
     fn find_previous_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
         let current_position = self.position();
-        self.text()
-            .chars()
+        self.cursor
+            .contents()
             .iter()
             .enumerate()
             .filter(|&(i, _)| i < current_position.saturating_sub(1))
             .rev()
-            .find(|&(_, c)| word_break_chars.contains(c))
+            .find(|&(_, g)| g.is_any_of(word_break_chars))
             .map(|(i, _)| i + 1)
             .unwrap_or(0)
     }
 
-    /// Erases the text from the current cursor position to the nearest previous character in `word_break_chars`.
-    pub fn erase_to_previous_nearest(&mut self, word_break_chars: &HashSet<char>) {
+    /// Erases the text from the current cursor position to the nearest previous character in `word_break_chars`,
+    /// returning the erased text.
+    pub fn erase_to_previous_nearest(&mut self, word_break_chars: &HashSet<char>) -> String {
         let pos = self.find_previous_nearest_index(word_break_chars);
-        self.erase_to_position(pos);
+        self.erase_to_position(pos)
     }
 
     /// Moves the cursor to the nearest previous character in `word_break_chars`.
     pub fn move_to_previous_nearest(&mut self, word_break_chars: &HashSet<char>) {
+        self.moved();
         let pos = self.find_previous_nearest_index(word_break_chars);
-        self.0.move_to(pos);
+        self.cursor.move_to(pos);
     }
 
     /// Finds the nearest next index of any character in `word_break_chars` from the cursor position.
     fn find_next_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
         let current_position = self.position();
-        self.text()
-            .chars()
+        self.cursor
+            .contents()
             .iter()
             .enumerate()
             .filter(|&(i, _)| i > current_position)
-            .find(|&(_, c)| word_break_chars.contains(c))
+            .find(|&(_, g)| g.is_any_of(word_break_chars))
             .map(|(i, _)| {
-                if i < self.0.contents().len() - 1 {
+                if i < self.cursor.contents().len() - 1 {
                     i + 1
                 } else {
-                    self.0.contents().len() - 1
+                    self.cursor.contents().len() - 1
                 }
             })
-            .unwrap_or(self.0.contents().len() - 1)
+            .unwrap_or(self.cursor.contents().len() - 1)
     }
 
-    /// Erases the text from the current cursor position to the nearest next character in `word_break_chars`.
-    pub fn erase_to_next_nearest(&mut self, word_break_chars: &HashSet<char>) {
+    /// Erases the text from the current cursor position to the nearest next character in `word_break_chars`,
+    /// returning the erased text.
+    pub fn erase_to_next_nearest(&mut self, word_break_chars: &HashSet<char>) -> String {
         let pos = self.find_next_nearest_index(word_break_chars);
-        self.erase_to_position(pos);
+        self.erase_to_position(pos)
     }
 
     /// Moves the cursor to the nearest next character in `word_break_chars`.
     pub fn move_to_next_nearest(&mut self, word_break_chars: &HashSet<char>) {
+        self.moved();
         let pos = self.find_next_nearest_index(word_break_chars);
-        self.0.move_to(pos);
+        self.cursor.move_to(pos);
     }
 
     /// Moves the cursor to the beginning of the text.
     pub fn move_to_head(&mut self) {
-        self.0.move_to_head()
+        self.moved();
+        self.cursor.move_to_head()
     }
 
     /// Moves the cursor to the end of the text.
     pub fn move_to_tail(&mut self) {
-        self.0.move_to_tail()
+        self.moved();
+        self.cursor.move_to_tail()
+    }
+
+    /// Moves the cursor to `position`, if it is within the text or at its end.
+    pub fn move_to(&mut self, position: usize) -> bool {
+        self.moved();
+        self.cursor.move_to(position)
     }
 
     pub fn shift(&mut self, backward: usize, forward: usize) -> bool {
-        self.0.shift(backward, forward)
+        self.moved();
+        self.cursor.shift(backward, forward)
     }
 
     /// Moves the cursor one position backward, if possible.
     pub fn backward(&mut self) -> bool {
-        self.0.backward()
+        self.moved();
+        self.cursor.backward()
     }
 
     /// Moves the cursor one position forward, if possible.
     pub fn forward(&mut self) -> bool {
-        self.0.forward()
+        self.moved();
+        self.cursor.forward()
+    }
+
+    /// Returns the range of the line containing `position`, excluding the line break after it.
+    pub fn line_range(&self, position: usize) -> Range<usize> {
+        let contents = self.cursor.contents();
+        let is_break = |i: &usize| contents[*i].as_str() == "\n";
+        let start = (0..position.min(self.len()))
+            .rev()
+            .find(is_break)
+            .map_or(0, |i| i + 1);
+        let end = (position..self.len()).find(is_break).unwrap_or(self.len());
+        start..end
+    }
+
+    /// Returns the number of lines in the text.
+    pub fn line_count(&self) -> usize {
+        1 + self
+            .cursor
+            .contents()
+            .iter()
+            .filter(|g| g.as_str() == "\n")
+            .count()
+    }
+
+    /// Returns the column the cursor keeps while moving vertically, if it is doing so.
+    pub fn goal_column(&self) -> Option<usize> {
+        self.goal_column
+    }
+
+    /// Moves the cursor to `position` on another line,
+    /// keeping `column` to return to on the lines after it.
+    pub fn move_vertically(&mut self, position: usize, column: usize) -> bool {
+        self.moved();
+        self.goal_column = Some(column);
+        self.cursor.move_to(position)
+    }
+
+    /// Moves the cursor to the same column on the previous line, or to its end if it is shorter.
+    /// Returns `false` on the first line.
+    pub fn move_up(&mut self) -> bool {
+        let current = self.line_range(self.position());
+        if current.start == 0 {
+            return false;
+        }
+        let column = self.goal_column.unwrap_or(self.position() - current.start);
+        let above = self.line_range(current.start - 1);
+        self.move_vertically(above.start + column.min(above.len()), column)
+    }
+
+    /// Moves the cursor to the same column on the next line, or to its end if it is shorter.
+    /// Returns `false` on the last line.
+    pub fn move_down(&mut self) -> bool {
+        let current = self.line_range(self.position());
+        if current.end >= self.len() {
+            return false;
+        }
+        let column = self.goal_column.unwrap_or(self.position() - current.start);
+        let below = self.line_range(current.end + 1);
+        self.move_vertically(below.start + column.min(below.len()), column)
+    }
+
+    /// Returns the range of the selected characters, if any are selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let pos = self.position();
+        (anchor != pos).then(|| anchor.min(pos)..anchor.max(pos))
+    }
+
+    /// Returns the selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        self.selection().map(|range| {
+            self.cursor
+                .contents()
+                .range(range)
+                .map(|g| g.as_str())
+                .collect()
+        })
+    }
+
+    /// Erases the selected text, returning it if any was selected.
+    pub fn erase_selection(&mut self) -> Option<String> {
+        let range = self.selection()?;
+        Some(self.erase_range(range))
+    }
+
+    /// Moves the cursor one position backward, extending the selection.
+    pub fn select_backward(&mut self) -> bool {
+        self.select(|cursor| cursor.backward())
+    }
+
+    /// Moves the cursor one position forward, extending the selection.
+    pub fn select_forward(&mut self) -> bool {
+        self.select(|cursor| cursor.forward())
+    }
+
+    /// Moves the cursor to the beginning of the text, extending the selection.
+    pub fn select_to_head(&mut self) {
+        self.select(|cursor| {
+            cursor.move_to_head();
+            true
+        });
+    }
+
+    /// Moves the cursor to the end of the text, extending the selection.
+    pub fn select_to_tail(&mut self) {
+        self.select(|cursor| {
+            cursor.move_to_tail();
+            true
+        });
+    }
+
+    /// Moves the cursor to `position`, extending the selection.
+    pub fn select_to(&mut self, position: usize) -> bool {
+        self.select(|cursor| cursor.move_to(position))
+    }
+
+    /// Moves the cursor with `f`, starting a selection at the cursor unless one is active.
+    fn select<F: FnOnce(&mut Cursor<StyledGraphemes>) -> bool>(&mut self, f: F) -> bool {
+        let anchor = self.anchor.unwrap_or(self.position());
+        self.moved();
+        let moved = f(&mut self.cursor);
+        self.anchor = Some(anchor);
+        moved
+    }
+
+    /// Ends the undo step and the selection after the cursor moved.
+    fn moved(&mut self) {
+        self.undo.seal();
+        self.anchor = None;
+        self.goal_column = None;
+    }
+
+    /// Records the state before `edit` to undo it, and ends the selection.
+    fn edited(&mut self, edit: Edit) {
+        self.undo.record(edit, &self.cursor);
+        self.anchor = None;
+        self.goal_column = None;
+    }
+
+    /// Reverts the last edit, restoring the text and the cursor position before it.
+    /// Consecutive inserted characters are reverted together.
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.anchor = None;
+        self.undo.undo(&mut self.cursor)
+    }
+
+    /// Reapplies the edit reverted last by [`Self::undo`], unless the text was edited since.
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.anchor = None;
+        self.undo.redo(&mut self.cursor)
     }
 }
 
@@ -227,7 +506,7 @@ mod test {
     use super::TextEditor;
 
     fn new_with_position(s: String, p: usize) -> TextEditor {
-        TextEditor(Cursor::new(StyledGraphemes::from(s), p, false))
+        TextEditor::from_cursor(Cursor::new(StyledGraphemes::from(s), p, false))
     }
 
     mod masking {
@@ -291,6 +570,77 @@ mod test {
             assert_eq!(StyledGraphemes::from("abc "), txt.text());
             assert_eq!(0, txt.position());
         }
+
+        #[test]
+        fn test_with_emoji_sequence() {
+            let mut txt = TextEditor::new("a👨\u{200D}👩\u{200D}👧");
+            assert_eq!(2, txt.position());
+            txt.erase();
+            assert_eq!(StyledGraphemes::from("a "), txt.text());
+            assert_eq!(1, txt.position());
+        }
+    }
+
+    mod erase_forward {
+        use crate::{grapheme::StyledGraphemes, text_editor::test::new_with_position};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("abc "), 1);
+            txt.erase_forward();
+            assert_eq!(StyledGraphemes::from("ac "), txt.text());
+            assert_eq!(1, txt.position());
+        }
+
+        #[test]
+        fn test_at_tail() {
+            let mut txt = new_with_position(String::from("abc "), 3);
+            txt.erase_forward();
+            assert_eq!(StyledGraphemes::from("abc "), txt.text());
+        }
+    }
+
+    mod erase_range {
+        use crate::{grapheme::StyledGraphemes, text_editor::test::new_with_position};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("abcde "), 4);
+            assert_eq!("bc", txt.erase_range(1..3));
+            assert_eq!(StyledGraphemes::from("ade "), txt.text());
+            assert_eq!(1, txt.position());
+        }
+
+        #[test]
+        fn test_beyond_tail() {
+            let mut txt = new_with_position(String::from("abc "), 0);
+            assert_eq!("bc", txt.erase_range(1..10));
+            assert_eq!(StyledGraphemes::from("a "), txt.text());
+        }
+    }
+
+    mod erase_to_head {
+        use crate::{grapheme::StyledGraphemes, text_editor::test::new_with_position};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("abc "), 2);
+            txt.erase_to_head();
+            assert_eq!(StyledGraphemes::from("c "), txt.text());
+            assert_eq!(0, txt.position());
+        }
+    }
+
+    mod erase_to_tail {
+        use crate::{grapheme::StyledGraphemes, text_editor::test::new_with_position};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("abc "), 1);
+            txt.erase_to_tail();
+            assert_eq!(StyledGraphemes::from("a "), txt.text());
+            assert_eq!(1, txt.position());
+        }
     }
 
     mod find_previous_nearest_index {
@@ -302,7 +652,7 @@ mod test {
         fn test() {
             let mut txt = new_with_position(String::from("koko momo jojo "), 11); // indicate `o`.
             assert_eq!(10, txt.find_previous_nearest_index(&HashSet::from([' '])));
-            txt.0.move_to(10);
+            txt.cursor.move_to(10);
             assert_eq!(5, txt.find_previous_nearest_index(&HashSet::from([' '])));
         }
 
@@ -322,7 +672,7 @@ mod test {
         fn test() {
             let mut txt = new_with_position(String::from("koko momo jojo "), 7); // indicate `m`.
             assert_eq!(10, txt.find_next_nearest_index(&HashSet::from([' '])));
-            txt.0.move_to(10);
+            txt.cursor.move_to(10);
             assert_eq!(14, txt.find_next_nearest_index(&HashSet::from([' '])));
         }
 
@@ -338,6 +688,16 @@ mod test {
 
         use super::super::*;
 
+        #[test]
+        fn test_with_combining_mark() {
+            let mut txt = TextEditor::default();
+            txt.insert('e');
+            txt.insert('\u{301}');
+            assert_eq!(StyledGraphemes::from("e\u{301} "), txt.text());
+            assert_eq!(2, txt.text().len());
+            assert_eq!(1, txt.position());
+        }
+
         #[test]
         fn test_for_empty() {
             let mut txt = TextEditor::default();
@@ -678,4 +1038,104 @@ mod test {
             assert_eq!(new.position(), txt.position());
         }
     }
+
+    mod undo {
+        use crate::text_editor::TextEditor;
+
+        #[test]
+        fn test_consecutive_inserts() {
+            let mut txt = TextEditor::new("abc");
+            for ch in "def".chars() {
+                txt.insert(ch);
+            }
+            txt.backward();
+            txt.insert('!');
+            assert_eq!("abcde!f", txt.text_without_cursor().to_string());
+
+            assert!(txt.undo());
+            assert_eq!("abcdef", txt.text_without_cursor().to_string());
+            assert!(txt.undo());
+            assert_eq!("abc", txt.text_without_cursor().to_string());
+            assert_eq!(3, txt.position());
+            assert!(!txt.undo());
+        }
+
+        #[test]
+        fn test_erase_all() {
+            let mut txt = TextEditor::new("abc");
+            txt.erase_all();
+            assert!(txt.undo());
+            assert_eq!("abc", txt.text_without_cursor().to_string());
+            assert_eq!(3, txt.position());
+        }
+
+        #[test]
+        fn test_redo() {
+            let mut txt = TextEditor::new("abc");
+            txt.erase();
+            txt.erase();
+            assert!(txt.undo());
+            assert!(txt.undo());
+            assert!(txt.redo());
+            assert_eq!("ab", txt.text_without_cursor().to_string());
+
+            txt.insert('x');
+            assert!(!txt.redo());
+            assert_eq!("abx", txt.text_without_cursor().to_string());
+        }
+    }
+
+    mod selection {
+        use crate::text_editor::TextEditor;
+
+        #[test]
+        fn test() {
+            let mut txt = TextEditor::new("abcd");
+            txt.select_backward();
+            txt.select_backward();
+            assert_eq!(Some(2..4), txt.selection());
+            assert_eq!(Some(String::from("cd")), txt.selected_text());
+
+            assert_eq!(Some(String::from("cd")), txt.erase_selection());
+            assert_eq!("ab", txt.text_without_cursor().to_string());
+            assert_eq!(None, txt.selection());
+        }
+
+        #[test]
+        fn test_cleared_by_moving() {
+            let mut txt = TextEditor::new("abcd");
+            txt.select_to_head();
+            assert_eq!(Some(0..4), txt.selection());
+            txt.forward();
+            assert_eq!(None, txt.selection());
+        }
+    }
+
+    mod move_up {
+        use crate::text_editor::TextEditor;
+
+        #[test]
+        fn test_keeps_column() {
+            let mut txt = TextEditor::new("abcd\nx\nabcd");
+            txt.backward();
+            assert!(txt.move_up());
+            assert_eq!(6, txt.position());
+            assert!(txt.move_up());
+            assert_eq!(3, txt.position());
+            assert!(!txt.move_up());
+        }
+    }
+
+    mod move_down {
+        use crate::text_editor::TextEditor;
+
+        #[test]
+        fn test() {
+            let mut txt = TextEditor::new("ab\nabcd");
+            txt.move_to(1);
+            assert!(txt.move_down());
+            assert_eq!(4, txt.position());
+            assert!(!txt.move_down());
+        }
+    }
 }
@@ -0,0 +1,141 @@
+use std::io::{self, IsTerminal, Write};
+
+use super::TextEditor;
+use crate::terminal;
+
+/// Text copied or cut from text editors, which can be pasted into them.
+///
+/// Copied text is also sent to the system clipboard with the OSC 52 escape sequence,
+/// which many terminals support without native dependencies, even over SSH.
+/// The prompt writes the sequence through its terminal (see [`crate::terminal::Terminal::copy_to_clipboard`])
+/// after the key that copied the text.
+/// Since terminals rarely allow reading the system clipboard,
+/// pasting inserts the text kept in the internal register instead,
+/// which also serves as the clipboard when the terminal ignores the sequence.
+#[derive(Clone)]
+pub struct Clipboard {
+    /// The text copied last.
+    register: String,
+    /// Text copied but not sent to the system clipboard yet.
+    unsent: Option<String>,
+
+    /// Whether copied text is sent to the system clipboard with OSC 52.
+    /// Disabled by default when stdout is not a terminal or in accessible mode
+    /// (see [`terminal::enable_accessible_mode`]).
+    pub osc52: bool,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self {
+            register: String::new(),
+            unsent: None,
+            osc52: io::stdout().is_terminal() && !terminal::accessible_mode_enabled(),
+        }
+    }
+}
+
+impl Clipboard {
+    /// Returns the text copied last.
+    pub fn get(&self) -> &str {
+        &self.register
+    }
+
+    /// Keeps `text` in the register and, if enabled, holds it to be sent to the system clipboard.
+    pub fn copy(&mut self, text: String) {
+        if self.osc52 {
+            self.unsent = Some(text.clone());
+        }
+        self.register = text;
+    }
+
+    /// Takes the text copied since the last call, to be sent to the system clipboard.
+    pub fn take_unsent(&mut self) -> Option<String> {
+        self.unsent.take()
+    }
+
+    /// Inserts the text copied last at the cursor of `texteditor`, replacing the selection.
+    /// Returns `false` if nothing has been copied.
+    pub fn paste(&self, texteditor: &mut TextEditor) -> bool {
+        if self.register.is_empty() {
+            return false;
+        }
+        texteditor.erase_selection();
+        texteditor.insert_chars(&self.register.chars().collect());
+        true
+    }
+}
+
+/// Writes the OSC 52 escape sequence that sets the system clipboard to `text`.
+pub fn write_osc52<W: Write>(out: &mut W, text: &str) -> io::Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()
+}
+
+/// Encodes `bytes` in base64 with padding, as OSC 52 expects.
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(TABLE[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    mod copy {
+        use crate::text_editor::Clipboard;
+
+        #[test]
+        fn test() {
+            let mut clipboard = Clipboard {
+                osc52: true,
+                ..Default::default()
+            };
+            clipboard.copy(String::from("foo"));
+            assert_eq!("foo", clipboard.get());
+            assert_eq!(Some(String::from("foo")), clipboard.take_unsent());
+            assert_eq!(None, clipboard.take_unsent());
+
+            clipboard.osc52 = false;
+            clipboard.copy(String::from("bar"));
+            assert_eq!("bar", clipboard.get());
+            assert_eq!(None, clipboard.take_unsent());
+        }
+    }
+
+    mod write_osc52 {
+        use crate::text_editor::clipboard::write_osc52;
+
+        #[test]
+        fn test() {
+            let mut out = Vec::new();
+            write_osc52(&mut out, "hello").unwrap();
+            assert_eq!(b"\x1b]52;c;aGVsbG8=\x07".to_vec(), out);
+        }
+    }
+
+    mod base64 {
+        use crate::text_editor::clipboard::base64;
+
+        #[test]
+        fn test() {
+            assert_eq!("", base64(b""));
+            assert_eq!("Zg==", base64(b"f"));
+            assert_eq!("Zm8=", base64(b"fo"));
+            assert_eq!("Zm9v", base64(b"foo"));
+            assert_eq!("44GC", base64("あ".as_bytes()));
+        }
+    }
+}
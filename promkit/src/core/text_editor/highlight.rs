@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes};
+
+/// Styles parts of the input of text editors as it is typed,
+/// e.g. keywords, strings and errors of SQL queries or shell commands.
+///
+/// It is implemented for closures with the same signature as [`Highlighter::highlight`].
+pub trait Highlighter {
+    /// Returns the styles for parts of `text`, each given by its range of byte offsets.
+    /// They replace the style of characters that are not active,
+    /// and are overridden by the styles of the selection and the cursor.
+    /// Later spans take precedence where they overlap.
+    fn highlight(&self, text: &str) -> Vec<(Range<usize>, ContentStyle)>;
+}
+
+impl<F> Highlighter for F
+where
+    F: Fn(&str) -> Vec<(Range<usize>, ContentStyle)>,
+{
+    fn highlight(&self, text: &str) -> Vec<(Range<usize>, ContentStyle)> {
+        self(text)
+    }
+}
+
+/// Applies the styles returned by `highlighter` for `text` to `graphemes`, which make it up.
+/// Each grapheme takes the style of the spans containing its first byte.
+pub(super) fn apply(
+    highlighter: &dyn Highlighter,
+    text: &str,
+    mut graphemes: StyledGraphemes,
+) -> StyledGraphemes {
+    let spans = highlighter.highlight(text);
+    let mut offset = 0;
+    for grapheme in graphemes.iter_mut() {
+        for (range, style) in &spans {
+            if range.contains(&offset) {
+                grapheme.apply_style(*style);
+            }
+        }
+        offset += grapheme.as_str().len();
+    }
+    graphemes
+}
+
+#[cfg(test)]
+mod test {
+    mod apply {
+        use std::ops::Range;
+
+        use crate::{
+            crossterm::style::{Color, ContentStyle},
+            grapheme::StyledGraphemes,
+            style::StyleBuilder,
+            text_editor::highlight::apply,
+        };
+
+        fn keywords(text: &str) -> Vec<(Range<usize>, ContentStyle)> {
+            text.match_indices("SELECT")
+                .map(|(i, keyword)| {
+                    (
+                        i..i + keyword.len(),
+                        StyleBuilder::new().fgc(Color::Blue).build(),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            let blue = StyleBuilder::new().fgc(Color::Blue).build();
+            let text = "あ SELECT 1";
+            let expect = [
+                StyledGraphemes::from("あ "),
+                StyledGraphemes::from_str("SELECT", blue),
+                StyledGraphemes::from(" 1"),
+            ]
+            .into_iter()
+            .collect::<StyledGraphemes>();
+            assert_eq!(expect, apply(&keywords, text, StyledGraphemes::from(text)));
+        }
+    }
+}
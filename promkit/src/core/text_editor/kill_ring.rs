@@ -0,0 +1,166 @@
+use std::{collections::VecDeque, ops::Range};
+
+use super::TextEditor;
+
+/// What the kill ring did last, which decides how the next kill or yank behaves.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+enum Last {
+    #[default]
+    Other,
+    /// Text was killed, so the next kill is joined to it.
+    Kill,
+    /// Text was yanked into this range of the editor, which can be replaced by rotating.
+    Yank(Range<usize>),
+}
+
+/// A ring of killed text in the style of Emacs and GNU Readline.
+/// Text erased by killing actions (e.g. <kbd>Ctrl + W</kbd>) is saved onto the ring,
+/// and can be inserted again by yanking (<kbd>Ctrl + Y</kbd>).
+/// Consecutive kills are joined into a single entry,
+/// and right after a yank, rotating (<kbd>Alt + Y</kbd>) replaces the yanked text
+/// with the entry killed before it.
+///
+/// Presets keep one ring for the whole prompt, which is shared by all fields of a form.
+/// Any other action has to be reported with [`KillRing::interrupt`]
+/// so that the next kill starts a new entry.
+#[derive(Clone, Default)]
+pub struct KillRing {
+    /// Killed texts, the most recent first.
+    entries: VecDeque<String>,
+    last: Last,
+
+    /// Optional limit on the number of entries in the ring.
+    /// If set, the oldest entries are dropped to make room for new ones.
+    pub limit_size: Option<usize>,
+}
+
+impl KillRing {
+    /// Returns the killed texts, the most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter()
+    }
+
+    /// Saves `text` onto the ring, joining it to the previous entry if that was killed just before.
+    /// `backward` tells whether the text was before the cursor,
+    /// in which case it is joined before the previous entry.
+    pub fn kill(&mut self, text: String, backward: bool) {
+        if text.is_empty() {
+            return;
+        }
+        match (&self.last, self.entries.front_mut()) {
+            (Last::Kill, Some(front)) if backward => front.insert_str(0, &text),
+            (Last::Kill, Some(front)) => front.push_str(&text),
+            _ => {
+                self.entries.push_front(text);
+                if let Some(limit) = self.limit_size {
+                    self.entries.truncate(limit.max(1));
+                }
+            }
+        }
+        self.last = Last::Kill;
+    }
+
+    /// Inserts the most recent entry at the cursor of `texteditor`.
+    /// Returns `false` if the ring is empty.
+    pub fn yank(&mut self, texteditor: &mut TextEditor) -> bool {
+        match self.entries.front() {
+            Some(text) => {
+                let start = texteditor.position();
+                texteditor.insert_chars(&text.chars().collect());
+                self.last = Last::Yank(start..texteditor.position());
+                true
+            }
+            None => {
+                self.interrupt();
+                false
+            }
+        }
+    }
+
+    /// Replaces the text inserted by the preceding yank or rotation
+    /// with the entry killed before it, moving the most recent entry to the end of the ring.
+    /// Returns `false` unless the previous operation was a yank or rotation.
+    pub fn rotate(&mut self, texteditor: &mut TextEditor) -> bool {
+        let Last::Yank(range) = self.last.clone() else {
+            return false;
+        };
+        texteditor.erase_range(range);
+        self.entries.rotate_left(1);
+        self.yank(texteditor)
+    }
+
+    /// Ends the current sequence of kills or yanks.
+    pub fn interrupt(&mut self) {
+        self.last = Last::Other;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod kill {
+        use crate::text_editor::KillRing;
+
+        #[test]
+        fn test_consecutive() {
+            let mut ring = KillRing::default();
+            ring.kill(String::from("world"), true);
+            ring.kill(String::from("hello "), true);
+            ring.kill(String::from("!"), false);
+            assert_eq!(vec!["hello world!"], ring.iter().collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_interrupted() {
+            let mut ring = KillRing::default();
+            ring.kill(String::from("world"), true);
+            ring.interrupt();
+            ring.kill(String::from("hello "), true);
+            assert_eq!(vec!["hello ", "world"], ring.iter().collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_with_limit_size() {
+            let mut ring = KillRing {
+                limit_size: Some(2),
+                ..Default::default()
+            };
+            for text in ["a", "b", "c"] {
+                ring.kill(String::from(text), false);
+                ring.interrupt();
+            }
+            assert_eq!(vec!["c", "b"], ring.iter().collect::<Vec<_>>());
+        }
+    }
+
+    mod rotate {
+        use crate::text_editor::{KillRing, TextEditor};
+
+        #[test]
+        fn test() {
+            let mut ring = KillRing::default();
+            for text in ["first", "second"] {
+                ring.kill(String::from(text), false);
+                ring.interrupt();
+            }
+            let mut texteditor = TextEditor::new("<>");
+            texteditor.backward();
+
+            assert!(ring.yank(&mut texteditor));
+            assert_eq!("<second>", texteditor.text_without_cursor().to_string());
+            assert!(ring.rotate(&mut texteditor));
+            assert_eq!("<first>", texteditor.text_without_cursor().to_string());
+            assert!(ring.rotate(&mut texteditor));
+            assert_eq!("<second>", texteditor.text_without_cursor().to_string());
+            assert_eq!(7, texteditor.position());
+        }
+
+        #[test]
+        fn test_without_yank() {
+            let mut ring = KillRing::default();
+            ring.kill(String::from("text"), false);
+            let mut texteditor = TextEditor::default();
+            assert!(!ring.rotate(&mut texteditor));
+            assert_eq!("", texteditor.text_without_cursor().to_string());
+        }
+    }
+}
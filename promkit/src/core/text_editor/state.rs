@@ -1,10 +1,32 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashSet, VecDeque},
+    rc::Rc,
+};
 
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    binding::Action,
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    pane::Pane,
+    PaneFactory,
+};
 
-use super::{History, Mode, TextEditor};
+use super::{highlight, Clipboard, Highlighter, History, KillRing, Mode, TextEditor};
 
-#[derive(Clone)]
+/// The state of a text editor to be rendered.
+///
+/// Fields not set explicitly can be taken from [`Default`],
+/// which has no prefix, plain styles and no optional features:
+///
+/// ```
+/// use promkit::text_editor::State;
+///
+/// let state = State {
+///     prefix: String::from("❯❯ "),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Default)]
 pub struct State {
     /// The `TextEditor` component to be rendered.
     pub texteditor: TextEditor,
@@ -13,6 +35,9 @@ pub struct State {
 
     /// Prompt string displayed before the input text.
     pub prefix: String,
+    /// Prompt string displayed before each line after the first (e.g. `.. `),
+    /// which are indented to align with the first line if `None`.
+    pub continuation_prefix: Option<String>,
     /// Optional character used for masking the input string (e.g., for password fields).
     pub mask: Option<char>,
 
@@ -22,6 +47,13 @@ pub struct State {
     pub active_char_style: ContentStyle,
     /// Style applied to characters that are not currently selected.
     pub inactive_char_style: ContentStyle,
+    /// Optional highlighter styling parts of the input instead of `inactive_char_style`,
+    /// which is not used for masked input.
+    pub highlighter: Option<Rc<dyn Highlighter>>,
+    /// Style applied to the characters selected with e.g. <kbd>Shift + ←</kbd>.
+    pub selection_style: ContentStyle,
+    /// Style applied to the numbers shown before each line, which are hidden if `None`.
+    pub line_number_style: Option<ContentStyle>,
 
     /// Current edit mode, determining whether input inserts or overwrites existing text.
     pub edit_mode: Mode,
@@ -31,38 +63,459 @@ pub struct State {
     pub lines: Option<usize>,
 }
 
-impl PaneFactory for State {
-    fn create_pane(&self, width: u16, height: u16) -> Pane {
-        let mut buf = StyledGraphemes::default();
+impl State {
+    /// Inserts or overwrites `ch` at the cursor, depending on the edit mode.
+    /// The selected text is replaced with it.
+    pub fn input(&mut self, ch: char) {
+        self.texteditor.erase_selection();
+        match self.edit_mode {
+            Mode::Insert => self.texteditor.insert(ch),
+            Mode::Overwrite => self.texteditor.overwrite(ch),
+        }
+    }
 
-        let mut styled_prefix = StyledGraphemes::from_str(&self.prefix, self.prefix_style);
+    /// Performs a text editing or history action,
+    /// returning `false` for actions that do not apply to text editors.
+    pub fn perform(&mut self, action: Action) -> bool {
+        let texteditor = &mut self.texteditor;
+        match action {
+            Action::MoveBackward => {
+                texteditor.backward();
+            }
+            Action::MoveForward => {
+                texteditor.forward();
+            }
+            Action::MoveToHead => texteditor.move_to_head(),
+            Action::MoveToTail => texteditor.move_to_tail(),
+            Action::MoveLineUp => {
+                texteditor.move_up();
+            }
+            Action::MoveLineDown => {
+                texteditor.move_down();
+            }
+            Action::InsertNewline => {
+                texteditor.erase_selection();
+                texteditor.insert('\n');
+            }
+            Action::MoveWordBackward => texteditor.move_to_previous_nearest(&self.word_break_chars),
+            Action::MoveWordForward => texteditor.move_to_next_nearest(&self.word_break_chars),
+            Action::EraseBackward => {
+                if texteditor.erase_selection().is_none() {
+                    texteditor.erase();
+                }
+            }
+            Action::EraseForward => {
+                if texteditor.erase_selection().is_none() {
+                    texteditor.erase_forward();
+                }
+            }
+            Action::SelectBackward => {
+                texteditor.select_backward();
+            }
+            Action::SelectForward => {
+                texteditor.select_forward();
+            }
+            Action::SelectToHead => texteditor.select_to_head(),
+            Action::SelectToTail => texteditor.select_to_tail(),
+            Action::EraseAll
+            | Action::EraseToHead
+            | Action::EraseToTail
+            | Action::EraseWordBackward
+            | Action::EraseWordForward => {
+                self.kill(action);
+            }
+            Action::Undo => {
+                texteditor.undo();
+            }
+            Action::Redo => {
+                texteditor.redo();
+            }
+            Action::HistoryPrevious => {
+                if let Some(history) = &mut self.history {
+                    if history.backward() {
+                        texteditor.replace(&history.get())
+                    }
+                }
+            }
+            Action::HistoryNext => {
+                if let Some(history) = &mut self.history {
+                    if history.forward() {
+                        texteditor.replace(&history.get())
+                    }
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
 
-        buf.append(&mut styled_prefix);
+    /// Performs `action` like [`Self::perform`], but saves the text erased by
+    /// killing actions (see [`Action::kills`]) onto `kill_ring`,
+    /// and yanks the text from it for [`Action::Yank`] and [`Action::YankPop`].
+    pub fn perform_with_kill_ring(&mut self, action: Action, kill_ring: &mut KillRing) -> bool {
+        match action {
+            Action::Yank => {
+                kill_ring.yank(&mut self.texteditor);
+            }
+            Action::YankPop => {
+                kill_ring.rotate(&mut self.texteditor);
+            }
+            action if action.kills() => {
+                if let Some(killed) = self.kill(action) {
+                    kill_ring.kill(
+                        killed,
+                        matches!(action, Action::EraseToHead | Action::EraseWordBackward),
+                    );
+                }
+            }
+            action => {
+                kill_ring.interrupt();
+                return self.perform(action);
+            }
+        }
+        true
+    }
+
+    /// Performs [`Action::Copy`], [`Action::Cut`] and [`Action::Paste`] with `clipboard`,
+    /// returning `false` for other actions.
+    /// Masked text (e.g. passwords) is never copied.
+    pub fn perform_with_clipboard(&mut self, action: Action, clipboard: &mut Clipboard) -> bool {
+        match action {
+            Action::Copy | Action::Cut if self.mask.is_some() => (),
+            Action::Copy => {
+                if let Some(text) = self.texteditor.selected_text() {
+                    clipboard.copy(text);
+                }
+            }
+            Action::Cut => {
+                if let Some(text) = self.texteditor.erase_selection() {
+                    clipboard.copy(text);
+                }
+            }
+            Action::Paste => {
+                clipboard.paste(&mut self.texteditor);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Erases the text for a killing action, returning the erased text.
+    fn kill(&mut self, action: Action) -> Option<String> {
+        let texteditor = &mut self.texteditor;
+        match action {
+            Action::EraseAll => Some(texteditor.erase_all()),
+            Action::EraseToHead => Some(texteditor.erase_to_head()),
+            Action::EraseToTail => Some(texteditor.erase_to_tail()),
+            Action::EraseWordBackward => {
+                Some(texteditor.erase_to_previous_nearest(&self.word_break_chars))
+            }
+            Action::EraseWordForward => {
+                Some(texteditor.erase_to_next_nearest(&self.word_break_chars))
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves the cursor to the row above in the pane created with the same `width`,
+    /// keeping its display column (see [`TextEditor::goal_column`]).
+    /// Returns `false` on the first row.
+    pub fn move_up_visually(&mut self, width: u16) -> bool {
+        self.move_visually(width, true)
+    }
+
+    /// Moves the cursor to the row below in the pane created with the same `width`,
+    /// keeping its display column. Returns `false` on the last row.
+    pub fn move_down_visually(&mut self, width: u16) -> bool {
+        self.move_visually(width, false)
+    }
+
+    fn move_visually(&mut self, width: u16, up: bool) -> bool {
+        let rows = self.layout(width);
+        let (row, column) = Self::coordinate_in(&rows, self.texteditor.position());
+        let target = if up {
+            row.checked_sub(1)
+        } else {
+            Some(row + 1).filter(|row| *row < rows.len())
+        };
+        let Some(target) = target else {
+            return false;
+        };
+        let goal = self.texteditor.goal_column().unwrap_or(column);
+        // The last character starting at or before the goal column, or the first one.
+        let mut found = None;
+        let mut column = 0;
+        for (g, position) in &rows[target] {
+            if let Some(position) = position {
+                if found.is_none() || column <= goal {
+                    found = Some(*position);
+                }
+            }
+            column += g.width();
+        }
+        match found {
+            Some(position) => self.texteditor.move_vertically(position, goal),
+            None => false,
+        }
+    }
 
+    /// Builds the (optionally masked) input text with the styles applied,
+    /// where line breaks are replaced with spaces so that the cursor can be shown on them.
+    fn styled_text(&self) -> StyledGraphemes {
         let text = match self.mask {
             Some(mask) => self.texteditor.masking(mask),
             None => self.texteditor.text(),
         };
+        let text = text
+            .iter()
+            .map(|g| match g.as_str() {
+                "\n" => StyledGrapheme::from(' '),
+                _ => g.clone(),
+            })
+            .collect::<StyledGraphemes>();
 
-        let mut styled = text
-            .apply_style(self.inactive_char_style)
-            .apply_style_at(self.texteditor.position(), self.active_char_style);
+        let mut text = text.apply_style(self.inactive_char_style);
+        if let (Some(highlighter), None) = (&self.highlighter, self.mask) {
+            let source = self.texteditor.text_without_cursor().to_string();
+            text = highlight::apply(highlighter.as_ref(), &source, text);
+        }
+        for i in self.texteditor.selection().unwrap_or_default() {
+            text = text.apply_style_at(i, self.selection_style);
+        }
+        text.apply_style_at(self.texteditor.position(), self.active_char_style)
+    }
+
+    /// Lays out the input in rows wrapped at `width`.
+    /// Each line of the input starts a new row, the first one after the prefix
+    /// and the others after the continuation prefix or indented to align with the first,
+    /// all after their numbers if enabled.
+    fn layout(&self, width: u16) -> Vec<Row> {
+        let prefix = StyledGraphemes::from_str(&self.prefix, self.prefix_style);
+        let indent = match &self.continuation_prefix {
+            Some(continuation) => StyledGraphemes::from_str(continuation, self.prefix_style),
+            None => StyledGraphemes::from(" ".repeat(prefix.widths())),
+        };
+        let digits = self.texteditor.line_count().to_string().len();
+        let head = |line: usize| {
+            let mut head = Row::new();
+            if let Some(style) = self.line_number_style {
+                let number = StyledGraphemes::from_str(format!("{:>digits$} ", line + 1), style);
+                head.extend(number.iter().map(|g| (g.clone(), None)));
+            }
+            let prefix = if line == 0 { &prefix } else { &indent };
+            head.extend(prefix.iter().map(|g| (g.clone(), None)));
+            head
+        };
 
-        buf.append(&mut styled);
+        let mut rows = vec![];
+        let mut line = 0;
+        let mut row = head(line);
+        let breaks = self.texteditor.text();
+        for (position, (g, original)) in self.styled_text().iter().zip(breaks.iter()).enumerate() {
+            row.push((g.clone(), Some(position)));
+            if original.as_str() == "\n" {
+                wrap(std::mem::take(&mut row), width as usize, &mut rows);
+                line += 1;
+                row = head(line);
+            }
+        }
+        wrap(row, width as usize, &mut rows);
+        rows
+    }
+
+    /// Returns the row and the display column of the grapheme at `position` within `rows`.
+    fn coordinate_in(rows: &[Row], position: usize) -> (usize, usize) {
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(index) = row.iter().position(|(_, p)| *p == Some(position)) {
+                let column = row.iter().take(index).map(|(g, _)| g.width()).sum();
+                return (i, column);
+            }
+        }
+        (rows.len().saturating_sub(1), 0)
+    }
 
+    /// Returns the row and the display column of the grapheme at `position`
+    /// within the pane created by `create_pane` with the same `width` and `height`.
+    fn coordinate(&self, position: usize, width: u16, height: u16) -> (usize, usize) {
+        let rows = self.layout(width);
+        let (row, column) = Self::coordinate_in(&rows, position);
         let height = match self.lines {
             Some(lines) => lines.min(height as usize),
             None => height as usize,
         };
+        // Mirrors the rows dropped from the top by `scroll` in `create_pane`.
+        (row - row.min(rows.len().saturating_sub(height)), column)
+    }
 
-        let (matrix, offset) = buf.matrixify(
-            width as usize,
-            height,
-            (StyledGraphemes::from_str(&self.prefix, self.prefix_style).widths()
-                + self.texteditor.position())
-                / width as usize,
-        );
+    /// Returns the position of the cursor as (row, column)
+    /// within the pane created by `create_pane` with the same `width` and `height`.
+    /// This is used to anchor popups (e.g. completion menus) next to the cursor.
+    pub fn cursor_coordinate(&self, width: u16, height: u16) -> (usize, usize) {
+        self.coordinate(self.texteditor.position(), width, height)
+    }
+
+    /// Returns the position of the start of the word at the cursor,
+    /// delimited by `word_break_chars` or a line break, as (row, column) like [`Self::cursor_coordinate`].
+    /// Popups anchored to it stay in place while the word is typed.
+    pub fn word_coordinate(&self, width: u16, height: u16) -> (usize, usize) {
+        let text = self.texteditor.text();
+        let mut start = self.texteditor.position();
+        while start > 0
+            && text[start - 1].as_str() != "\n"
+            && !text[start - 1].is_any_of(&self.word_break_chars)
+        {
+            start -= 1;
+        }
+        self.coordinate(start, width, height)
+    }
+}
+
+/// A row of the input laid out in a pane, with the position in the text of each grapheme,
+/// which is `None` for prefixes and line numbers.
+type Row = Vec<(StyledGrapheme, Option<usize>)>;
+
+/// Appends `line` to `rows`, broken into rows of at most `width` columns at any character.
+fn wrap(line: Row, width: usize, rows: &mut Vec<Row>) {
+    let mut row = Row::new();
+    let mut widths = 0;
+    for (g, position) in line {
+        if !row.is_empty() && width < widths + g.width() {
+            rows.push(std::mem::take(&mut row));
+            widths = 0;
+        }
+        if width >= g.width() {
+            widths += g.width();
+            row.push((g, position));
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+}
+
+/// Drops rows to fit `height`, first from the top until the row at `offset` comes first
+/// and then from the bottom, in the same way as [`StyledGraphemes::matrixify`].
+fn scroll(rows: Vec<Row>, height: usize, offset: usize) -> (Vec<StyledGraphemes>, usize) {
+    let mut rows = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(|(g, _)| g).collect::<StyledGraphemes>())
+        .collect::<VecDeque<_>>();
+    let mut offset = offset.min(rows.len().saturating_sub(1));
+    while rows.len() > height && offset < rows.len() {
+        if offset > 0 {
+            rows.pop_front();
+            offset -= 1;
+        } else {
+            rows.pop_back();
+        }
+    }
+    (Vec::from(rows), offset)
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+
+        let rows = self.layout(width);
+        let (row, _) = Self::coordinate_in(&rows, self.texteditor.position());
+        let (matrix, offset) = scroll(rows, height, row);
 
         Pane::new(matrix, offset)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::text_editor::{State, TextEditor};
+
+    fn new_state(text: &str, lines: Option<usize>) -> State {
+        State {
+            texteditor: TextEditor::new(text),
+            prefix: String::from("> "),
+            lines,
+            ..Default::default()
+        }
+    }
+
+    mod cursor_coordinate {
+        use crate::crossterm::style::ContentStyle;
+
+        use super::new_state;
+
+        #[test]
+        fn test() {
+            assert_eq!((0, 5), new_state("abc", None).cursor_coordinate(10, 10));
+        }
+
+        #[test]
+        fn test_with_wrapping() {
+            assert_eq!(
+                (1, 3),
+                new_state("abcdefghijk", None).cursor_coordinate(10, 10)
+            );
+        }
+
+        #[test]
+        fn test_with_line_breaks() {
+            let mut state = new_state("ab\ncd", None);
+            assert_eq!((1, 4), state.cursor_coordinate(10, 10));
+            state.line_number_style = Some(ContentStyle::default());
+            assert_eq!((1, 6), state.cursor_coordinate(10, 10));
+        }
+
+        #[test]
+        fn test_with_continuation_prefix() {
+            let mut state = new_state("ab\ncd", None);
+            state.continuation_prefix = Some(String::from("... "));
+            assert_eq!((1, 6), state.cursor_coordinate(10, 10));
+        }
+
+        #[test]
+        fn test_with_limited_lines() {
+            assert_eq!(
+                (0, 3),
+                new_state("abcdefghijk", Some(1)).cursor_coordinate(10, 10)
+            );
+        }
+    }
+
+    mod word_coordinate {
+        use std::collections::HashSet;
+
+        use super::new_state;
+
+        #[test]
+        fn test() {
+            let mut state = new_state("git com", None);
+            state.word_break_chars = HashSet::from([' ']);
+            assert_eq!((0, 6), state.word_coordinate(20, 10));
+            state.texteditor.insert('m');
+            assert_eq!((0, 6), state.word_coordinate(20, 10));
+            assert_eq!((0, 10), state.cursor_coordinate(20, 10));
+        }
+
+        #[test]
+        fn test_with_line_breaks() {
+            let mut state = new_state("git\ncom", None);
+            state.word_break_chars = HashSet::from([' ']);
+            assert_eq!((1, 2), state.word_coordinate(20, 10));
+        }
+    }
+
+    mod move_up_visually {
+        use super::new_state;
+
+        #[test]
+        fn test_keeps_column() {
+            let mut state = new_state("abcdefghijk", None);
+            assert!(state.move_up_visually(10));
+            assert_eq!(1, state.texteditor.position());
+            assert!(!state.move_up_visually(10));
+            assert!(state.move_down_visually(10));
+            assert_eq!(11, state.texteditor.position());
+        }
+    }
+}
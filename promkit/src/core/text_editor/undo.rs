@@ -0,0 +1,63 @@
+use crate::{core::cursor::Cursor, grapheme::StyledGraphemes};
+
+/// The kind of an edit, which decides whether it joins the previous undo step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Edit {
+    /// Typing a character. Consecutive inserts are undone together.
+    Insert,
+    /// Any other change of the text, undone on its own.
+    Other,
+}
+
+/// Previous and undone states of a [`TextEditor`](super::TextEditor),
+/// each being the text and the cursor position before an undo step.
+#[derive(Clone, Default)]
+pub(super) struct UndoStack {
+    undo: Vec<Cursor<StyledGraphemes>>,
+    redo: Vec<Cursor<StyledGraphemes>>,
+    /// The kind of the last edit if nothing else happened since then.
+    last: Option<Edit>,
+}
+
+impl UndoStack {
+    /// Records `current`, the state before `edit`, as a new undo step
+    /// unless the edit joins the previous step. Edits made after undoing can no longer be redone.
+    pub(super) fn record(&mut self, edit: Edit, current: &Cursor<StyledGraphemes>) {
+        if !(edit == Edit::Insert && self.last == Some(Edit::Insert)) {
+            self.undo.push(current.clone());
+        }
+        self.redo.clear();
+        self.last = Some(edit);
+    }
+
+    /// Ends the current undo step, e.g. when the cursor moves between inserts.
+    pub(super) fn seal(&mut self) {
+        self.last = None;
+    }
+
+    /// Restores the state before the last undo step into `current`.
+    /// Returns `false` if there is nothing to undo.
+    pub(super) fn undo(&mut self, current: &mut Cursor<StyledGraphemes>) -> bool {
+        self.seal();
+        match self.undo.pop() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restores the state before the last undo into `current`.
+    /// Returns `false` if there is nothing to redo.
+    pub(super) fn redo(&mut self, current: &mut Cursor<StyledGraphemes>) -> bool {
+        self.seal();
+        match self.redo.pop() {
+            Some(next) => {
+                self.undo.push(std::mem::replace(current, next));
+                true
+            }
+            None => false,
+        }
+    }
+}
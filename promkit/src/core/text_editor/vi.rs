@@ -0,0 +1,888 @@
+//! Modal editing in the style of vi for [`State`].
+//!
+//! [`Vi`] interprets keys according to its current [`ViMode`] and edits the state,
+//! while presets register a keymap for each mode (see [`ViMode::keymap_name`])
+//! and switch between them when a key changes the mode.
+//!
+//! The normal mode supports the motions `h l w b e 0 ^ $ f F t T`,
+//! the operators `d c y` (doubled to act on the whole input) with counts,
+//! `x X D C s p P`, the insert commands `i a I A`, `.` to repeat the last change,
+//! and `u` and <kbd>Ctrl + R</kbd> to undo and redo.
+//! The visual mode selects text with the same motions and applies `d c y` to it.
+
+use crate::{
+    binding::{Action, KeyPress},
+    crossterm::event::{KeyCode, KeyModifiers},
+    keyhint::KeyHint,
+    locale,
+};
+
+use super::State;
+
+/// The largest count of a command; larger counts are clamped to it,
+/// so that e.g. `99999999p` does not insert the register that many times.
+const MAX_COUNT: usize = 9999;
+
+/// A mode of vi editing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ViMode {
+    /// Typed characters are inserted.
+    #[default]
+    Insert,
+    /// Keys are commands.
+    Normal,
+    /// Keys move the end of a selection, to which operators are applied.
+    Visual,
+}
+
+impl ViMode {
+    /// Returns the name of the keymap presets register for the mode.
+    pub fn keymap_name(&self) -> &'static str {
+        match self {
+            ViMode::Insert => "vi_insert",
+            ViMode::Normal => "vi_normal",
+            ViMode::Visual => "vi_visual",
+        }
+    }
+}
+
+/// The result of handling a key with [`Vi`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The key was handled.
+    Handled,
+    /// The key was handled and changed the mode,
+    /// so the keymap of the new mode should be activated.
+    Switched(ViMode),
+    /// The key means nothing in the insert mode,
+    /// and is left to the default keymap of the preset.
+    Unhandled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Head,
+    FirstNonBlank,
+    Tail,
+    WordForward,
+    WordBackward,
+    WordEnd,
+    /// The end of the word under the cursor, which `cw` changes to.
+    CurrentWordEnd,
+    /// `f`, `F`, `t` or `T` followed by the target character.
+    Find(char, char),
+}
+
+impl Motion {
+    /// Whether an operator acts on the character at the target as well.
+    fn inclusive(&self) -> bool {
+        matches!(
+            self,
+            Motion::Tail
+                | Motion::WordEnd
+                | Motion::CurrentWordEnd
+                | Motion::Find('f', _)
+                | Motion::Find('t', _)
+        )
+    }
+
+    /// Returns the position the motion moves to from `pos`, or `None` if it fails.
+    fn target(&self, chars: &[char], pos: usize, count: usize) -> Option<usize> {
+        let len = chars.len();
+        // No motion moves over more characters than there are.
+        let count = count.min(len.max(1));
+        match *self {
+            Motion::Left => Some(pos.saturating_sub(count)),
+            Motion::Right => Some(pos.saturating_add(count).min(len)),
+            Motion::Head => Some(0),
+            Motion::FirstNonBlank => Some(
+                chars
+                    .iter()
+                    .position(|ch| !ch.is_whitespace())
+                    .unwrap_or(len.saturating_sub(1)),
+            ),
+            Motion::Tail => Some(len.saturating_sub(1)),
+            Motion::WordForward => Some((0..count).fold(pos, |pos, _| next_word_start(chars, pos))),
+            Motion::WordBackward => {
+                Some((0..count).fold(pos, |pos, _| previous_word_start(chars, pos)))
+            }
+            Motion::WordEnd => Some((0..count).fold(pos, |pos, _| word_end(chars, pos))),
+            Motion::CurrentWordEnd => {
+                let mut end = pos;
+                while end + 1 < len && class(chars[end + 1]) == class(chars[pos]) {
+                    end += 1;
+                }
+                Some((1..count).fold(end, |pos, _| word_end(chars, pos)))
+            }
+            Motion::Find(kind, target) => {
+                let forward = kind == 'f' || kind == 't';
+                let found = if forward {
+                    chars
+                        .iter()
+                        .enumerate()
+                        .skip(pos + 1)
+                        .filter(|(_, ch)| **ch == target)
+                        .nth(count - 1)
+                } else {
+                    chars
+                        .iter()
+                        .enumerate()
+                        .take(pos)
+                        .rev()
+                        .filter(|(_, ch)| **ch == target)
+                        .nth(count - 1)
+                };
+                found.map(|(i, _)| match kind {
+                    't' => i - 1,
+                    'T' => i + 1,
+                    _ => i,
+                })
+            }
+        }
+    }
+}
+
+/// Whitespace, word characters and punctuation form separate classes,
+/// and a word is a run of characters of the same class other than whitespace.
+fn class(ch: char) -> u8 {
+    if ch.is_whitespace() {
+        0
+    } else if ch.is_alphanumeric() || ch == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn next_word_start(chars: &[char], pos: usize) -> usize {
+    let mut i = pos;
+    if let Some(&ch) = chars.get(i) {
+        let start = class(ch);
+        while i < chars.len() && start != 0 && class(chars[i]) == start {
+            i += 1;
+        }
+    }
+    while i < chars.len() && class(chars[i]) == 0 {
+        i += 1;
+    }
+    i
+}
+
+fn previous_word_start(chars: &[char], pos: usize) -> usize {
+    let mut i = pos.min(chars.len());
+    while i > 0 && class(chars[i - 1]) == 0 {
+        i -= 1;
+    }
+    if i == 0 {
+        return 0;
+    }
+    let class_of_word = class(chars[i - 1]);
+    while i > 0 && class(chars[i - 1]) == class_of_word {
+        i -= 1;
+    }
+    i
+}
+
+fn word_end(chars: &[char], pos: usize) -> usize {
+    let mut i = pos + 1;
+    while i < chars.len() && class(chars[i]) == 0 {
+        i += 1;
+    }
+    if i >= chars.len() {
+        return chars.len().saturating_sub(1);
+    }
+    let class_of_word = class(chars[i]);
+    while i + 1 < chars.len() && class(chars[i + 1]) == class_of_word {
+        i += 1;
+    }
+    i
+}
+
+/// Returns the character of a key pressed without modifiers.
+fn plain_char(key: KeyPress) -> Option<char> {
+    match key.code {
+        KeyCode::Char(ch) if key.modifiers == KeyModifiers::NONE => Some(ch),
+        _ => None,
+    }
+}
+
+/// The state of vi editing: the current mode, a command typed halfway,
+/// the register holding yanked and deleted text, and the last change for `.`.
+#[derive(Clone, Debug)]
+pub struct Vi {
+    mode: ViMode,
+    count: Option<usize>,
+    operator: Option<(Operator, usize)>,
+    /// `f`, `F`, `t` or `T` waiting for the target character.
+    find: Option<char>,
+    register: String,
+
+    /// Keys of the command being typed in the normal mode.
+    keys: Vec<KeyPress>,
+    /// Keys of a change that continues in the insert mode, e.g. `cw` followed by the new word.
+    recording: Option<Vec<KeyPress>>,
+    last_change: Vec<KeyPress>,
+    replaying: bool,
+
+    /// Indicators shown before the prefix, taken from the message catalog.
+    indicators: [String; 3],
+}
+
+impl Default for Vi {
+    fn default() -> Self {
+        let messages = locale::messages();
+        Self {
+            mode: Default::default(),
+            count: Default::default(),
+            operator: Default::default(),
+            find: Default::default(),
+            register: Default::default(),
+            keys: Default::default(),
+            recording: Default::default(),
+            last_change: Default::default(),
+            replaying: Default::default(),
+            indicators: [
+                messages.vi_insert_indicator,
+                messages.vi_normal_indicator,
+                messages.vi_visual_indicator,
+            ],
+        }
+    }
+}
+
+impl Vi {
+    /// Returns the current mode.
+    pub fn mode(&self) -> ViMode {
+        self.mode
+    }
+
+    /// Returns the text last yanked or deleted.
+    pub fn register(&self) -> &str {
+        &self.register
+    }
+
+    fn indicator(&self, mode: ViMode) -> &str {
+        match mode {
+            ViMode::Insert => &self.indicators[0],
+            ViMode::Normal => &self.indicators[1],
+            ViMode::Visual => &self.indicators[2],
+        }
+    }
+
+    /// Puts the indicator of the current mode before the prefix of `state`.
+    pub fn show_indicator(&self, state: &mut State) {
+        self.hide_indicator(state);
+        state.prefix = format!("{}{}", self.indicator(self.mode), state.prefix);
+    }
+
+    /// Removes the indicator of the current mode from the prefix of `state`.
+    pub fn hide_indicator(&self, state: &mut State) {
+        if let Some(prefix) = state.prefix.strip_prefix(self.indicator(self.mode)) {
+            state.prefix = prefix.to_string();
+        }
+    }
+
+    fn switch(&mut self, mode: ViMode, state: &mut State) -> Outcome {
+        self.hide_indicator(state);
+        self.mode = mode;
+        self.show_indicator(state);
+        Outcome::Switched(mode)
+    }
+
+    /// Forgets a command typed halfway.
+    fn reset(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.find = None;
+        self.keys.clear();
+    }
+
+    fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    /// Completes the command being typed, remembering it for `.` if it is a change.
+    fn finish(&mut self, change: bool) {
+        if change && !self.replaying {
+            self.last_change = std::mem::take(&mut self.keys);
+        }
+        self.reset();
+    }
+
+    /// Keeps recording the command being typed in the insert mode that follows.
+    fn finish_into_insert(&mut self, state: &mut State) -> Outcome {
+        if !self.replaying {
+            self.recording = Some(std::mem::take(&mut self.keys));
+        }
+        self.reset();
+        self.switch(ViMode::Insert, state)
+    }
+
+    /// Handles a key in the insert mode.
+    pub fn insert(&mut self, key: KeyPress, state: &mut State) -> Outcome {
+        let outcome = match (key.code, plain_char(key)) {
+            (KeyCode::Esc, _) if key.modifiers.is_empty() => {
+                if let Some(mut keys) = self.recording.take() {
+                    keys.push(key);
+                    self.last_change = keys;
+                }
+                state.texteditor.backward();
+                return self.switch(ViMode::Normal, state);
+            }
+            (_, Some(ch)) => {
+                state.input(ch);
+                Outcome::Handled
+            }
+            (KeyCode::Backspace, _) if key.modifiers.is_empty() => {
+                state.texteditor.erase();
+                Outcome::Handled
+            }
+            _ => return Outcome::Unhandled,
+        };
+        if !self.replaying {
+            if let Some(keys) = &mut self.recording {
+                keys.push(key);
+            }
+        }
+        outcome
+    }
+
+    /// Handles a key in the normal mode.
+    pub fn normal(&mut self, key: KeyPress, state: &mut State) -> Outcome {
+        if !self.replaying {
+            self.keys.push(key);
+        }
+        if let Some(outcome) = self.motion_key(key, state) {
+            return outcome;
+        }
+        if key == KeyPress::ctrl('r') {
+            self.reset();
+            state.texteditor.redo();
+            return Outcome::Handled;
+        }
+        let Some(ch) = plain_char(key) else {
+            self.reset();
+            return Outcome::Handled;
+        };
+        let len = state.texteditor.len();
+        match ch {
+            'd' | 'c' | 'y' => {
+                let operator = match ch {
+                    'd' => Operator::Delete,
+                    'c' => Operator::Change,
+                    _ => Operator::Yank,
+                };
+                match self.operator {
+                    // Doubled operators act on the whole input.
+                    Some((pending, _)) if pending == operator => {
+                        state.texteditor.move_to_head();
+                        self.operate(operator, 0, len, state)
+                    }
+                    Some(_) => {
+                        self.reset();
+                        Outcome::Handled
+                    }
+                    None => {
+                        let count = self.take_count();
+                        self.operator = Some((operator, count));
+                        Outcome::Handled
+                    }
+                }
+            }
+            'x' | 'X' | 's' | 'D' | 'C' => {
+                let (operator, motion) = match ch {
+                    'x' => (Operator::Delete, Motion::Right),
+                    'X' => (Operator::Delete, Motion::Left),
+                    's' => (Operator::Change, Motion::Right),
+                    'D' => (Operator::Delete, Motion::Tail),
+                    _ => (Operator::Change, Motion::Tail),
+                };
+                self.operator = Some((operator, 1));
+                self.motion(motion, state)
+            }
+            'p' | 'P' => {
+                let count = self.take_count();
+                if self.register.is_empty() {
+                    self.finish(false);
+                    return Outcome::Handled;
+                }
+                if ch == 'p' && len > 0 {
+                    state.texteditor.forward();
+                }
+                let chars = self.register.repeat(count).chars().collect();
+                state.texteditor.insert_chars(&chars);
+                state.texteditor.backward();
+                self.finish(true);
+                Outcome::Handled
+            }
+            'i' | 'a' | 'I' | 'A' => {
+                match ch {
+                    'a' if len > 0 => {
+                        state.texteditor.forward();
+                    }
+                    'I' => state.texteditor.move_to_head(),
+                    'A' => state.texteditor.move_to_tail(),
+                    _ => (),
+                }
+                self.finish_into_insert(state)
+            }
+            'v' => {
+                self.reset();
+                state.texteditor.select_to(state.texteditor.position());
+                self.switch(ViMode::Visual, state)
+            }
+            'u' => {
+                self.reset();
+                state.texteditor.undo();
+                Outcome::Handled
+            }
+            '.' => {
+                let count = self.count.take();
+                self.reset();
+                self.repeat(count, state);
+                Outcome::Handled
+            }
+            _ => {
+                self.reset();
+                Outcome::Handled
+            }
+        }
+    }
+
+    /// Handles a key in the visual mode.
+    pub fn visual(&mut self, key: KeyPress, state: &mut State) -> Outcome {
+        if self.find.is_none() {
+            let operator = match (key.code, plain_char(key)) {
+                (_, Some('d' | 'x')) => Some(Operator::Delete),
+                (_, Some('c' | 's')) => Some(Operator::Change),
+                (_, Some('y')) => Some(Operator::Yank),
+                (KeyCode::Esc, _) | (_, Some('v')) => {
+                    self.reset();
+                    // Moving in place ends the selection.
+                    state.texteditor.move_to(state.texteditor.position());
+                    return self.switch(ViMode::Normal, state);
+                }
+                _ => None,
+            };
+            if let Some(operator) = operator {
+                let pos = state.texteditor.position();
+                // The selection ends before the cursor, but the visual mode includes it.
+                let range = state.texteditor.selection().unwrap_or(pos..pos);
+                let (start, end) = (range.start, range.end + 1);
+                self.reset();
+                state.texteditor.move_to(start);
+                let outcome = self.operate(operator, start, end, state);
+                return match outcome {
+                    Outcome::Switched(_) => outcome,
+                    _ => self.switch(ViMode::Normal, state),
+                };
+            }
+        }
+        self.motion_key(key, state).unwrap_or_else(|| {
+            self.reset();
+            Outcome::Handled
+        })
+    }
+
+    /// Handles counts and motions, returning `None` for other keys.
+    fn motion_key(&mut self, key: KeyPress, state: &mut State) -> Option<Outcome> {
+        if let Some(kind) = self.find.take() {
+            return Some(match plain_char(key) {
+                Some(target) => self.motion(Motion::Find(kind, target), state),
+                None => {
+                    self.reset();
+                    Outcome::Handled
+                }
+            });
+        }
+        let motion = match (key.code, plain_char(key)) {
+            (_, Some(digit @ '0'..='9')) if digit != '0' || self.count.is_some() => {
+                let digit = digit.to_digit(10).unwrap_or_default() as usize;
+                self.count = Some(
+                    self.count
+                        .unwrap_or_default()
+                        .saturating_mul(10)
+                        .saturating_add(digit)
+                        .min(MAX_COUNT),
+                );
+                return Some(Outcome::Handled);
+            }
+            (_, Some(kind @ ('f' | 'F' | 't' | 'T'))) => {
+                self.find = Some(kind);
+                return Some(Outcome::Handled);
+            }
+            (KeyCode::Esc, _) => {
+                self.reset();
+                return Some(Outcome::Handled);
+            }
+            (KeyCode::Left, _) | (_, Some('h')) => Motion::Left,
+            (KeyCode::Right, _) | (_, Some('l')) => Motion::Right,
+            (KeyCode::Home, _) | (_, Some('0')) => Motion::Head,
+            (_, Some('^')) => Motion::FirstNonBlank,
+            (KeyCode::End, _) | (_, Some('$')) => Motion::Tail,
+            (_, Some('w')) => Motion::WordForward,
+            (_, Some('b')) => Motion::WordBackward,
+            (_, Some('e')) => Motion::WordEnd,
+            _ => return None,
+        };
+        Some(self.motion(motion, state))
+    }
+
+    /// Moves the cursor, or applies the pending operator to the text moved over.
+    fn motion(&mut self, motion: Motion, state: &mut State) -> Outcome {
+        let count = self.take_count();
+        let chars = state
+            .texteditor
+            .text_without_cursor()
+            .iter()
+            .map(|grapheme| grapheme.as_str().chars().next().unwrap_or(' '))
+            .collect::<Vec<_>>();
+        let pos = state.texteditor.position();
+
+        let Some((operator, operator_count)) = self.operator.take() else {
+            if let Some(target) = motion.target(&chars, pos, count) {
+                // In the normal mode the cursor stays on a character.
+                let last = match self.mode {
+                    ViMode::Insert => chars.len(),
+                    _ => chars.len().saturating_sub(1),
+                };
+                // The visual mode extends the selection from where it was entered.
+                match self.mode {
+                    ViMode::Visual => state.texteditor.select_to(target.min(last)),
+                    _ => state.texteditor.move_to(target.min(last)),
+                };
+            }
+            self.finish(false);
+            return Outcome::Handled;
+        };
+
+        // `cw` changes to the end of the word under the cursor.
+        let motion = match motion {
+            Motion::WordForward
+                if operator == Operator::Change
+                    && chars.get(pos).is_some_and(|ch| !ch.is_whitespace()) =>
+            {
+                Motion::CurrentWordEnd
+            }
+            motion => motion,
+        };
+        let Some(target) = motion.target(&chars, pos, count.saturating_mul(operator_count)) else {
+            self.reset();
+            return Outcome::Handled;
+        };
+        let (start, mut end) = (pos.min(target), pos.max(target));
+        if motion.inclusive() {
+            end += 1;
+        }
+        state.texteditor.move_to(start);
+        self.operate(operator, start, end, state)
+    }
+
+    fn operate(
+        &mut self,
+        operator: Operator,
+        start: usize,
+        end: usize,
+        state: &mut State,
+    ) -> Outcome {
+        match operator {
+            Operator::Yank => {
+                self.register = state
+                    .texteditor
+                    .text_without_cursor()
+                    .iter()
+                    .skip(start)
+                    .take(end.saturating_sub(start))
+                    .map(|grapheme| grapheme.as_str())
+                    .collect();
+                self.finish(false);
+                Outcome::Handled
+            }
+            Operator::Delete => {
+                self.register = state.texteditor.erase_range(start..end);
+                if state.texteditor.position() >= state.texteditor.len() {
+                    state.texteditor.backward();
+                }
+                self.finish(true);
+                Outcome::Handled
+            }
+            Operator::Change => {
+                self.register = state.texteditor.erase_range(start..end);
+                self.finish_into_insert(state)
+            }
+        }
+    }
+
+    /// Replays the last change, with `count` in place of its own count if given.
+    fn repeat(&mut self, count: Option<usize>, state: &mut State) {
+        let mut keys = self.last_change.clone();
+        if let Some(count) = count {
+            let digits = keys
+                .iter()
+                .take_while(|key| plain_char(**key).is_some_and(|ch| ch.is_ascii_digit()))
+                .count();
+            keys.splice(
+                ..digits,
+                count
+                    .to_string()
+                    .chars()
+                    .map(|digit| KeyPress::plain(KeyCode::Char(digit))),
+            );
+        }
+        self.replaying = true;
+        for key in keys {
+            match self.mode {
+                ViMode::Insert => self.insert(key, state),
+                _ => self.normal(key, state),
+            };
+        }
+        self.replaying = false;
+    }
+}
+
+/// Key hints for the insert mode: the hints of a preset `hints`,
+/// with the key to leave the insert mode after the first of them.
+pub fn insert_hints(mut hints: Vec<KeyHint>) -> Vec<KeyHint> {
+    let index = hints.len().min(1);
+    hints.insert(
+        index,
+        KeyHint::new("Esc", locale::messages().hint_vi_normal),
+    );
+    hints
+}
+
+/// Key hints for the normal mode, to be combined with the hints of a preset.
+pub fn normal_hints() -> Vec<KeyHint> {
+    let messages = locale::messages();
+    vec![
+        KeyHint::new("i/a", &messages.hint_vi_insert),
+        KeyHint::new("v", &messages.hint_vi_visual),
+        KeyHint::new("w/b/e", messages.action(Action::MoveWordForward)),
+        KeyHint::new("d/c/y", &messages.hint_vi_operators),
+        KeyHint::new("p/P", messages.action(Action::Paste)),
+        KeyHint::new(".", &messages.hint_repeat),
+        KeyHint::new("u", messages.action(Action::Undo)),
+        KeyHint::new("Ctrl+R", messages.action(Action::Redo)),
+    ]
+}
+
+/// Key hints for the visual mode, to be combined with the hints of a preset.
+pub fn visual_hints() -> Vec<KeyHint> {
+    let messages = locale::messages();
+    vec![
+        KeyHint::new("d/c/y", &messages.hint_vi_operators),
+        KeyHint::new("Esc", &messages.hint_vi_normal),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        binding::KeyPress,
+        text_editor::{vi::Vi, State, TextEditor},
+    };
+
+    fn new_state(text: &str) -> State {
+        State {
+            texteditor: TextEditor::new(text),
+            prefix: String::from("❯ "),
+            ..Default::default()
+        }
+    }
+
+    /// Types `keys` in the normal mode, or in the insert mode after a key entered it.
+    fn type_keys(vi: &mut Vi, state: &mut State, keys: &str) {
+        for ch in keys.chars() {
+            let key = match ch {
+                '⎋' => "esc".parse::<KeyPress>().unwrap(),
+                ch => KeyPress::plain(crate::crossterm::event::KeyCode::Char(ch)),
+            };
+            match vi.mode() {
+                super::ViMode::Insert => vi.insert(key, state),
+                super::ViMode::Normal => vi.normal(key, state),
+                super::ViMode::Visual => vi.visual(key, state),
+            };
+        }
+    }
+
+    fn normal(text: &str) -> (Vi, State) {
+        let mut vi = Vi::default();
+        let mut state = new_state(text);
+        type_keys(&mut vi, &mut state, "⎋0");
+        (vi, state)
+    }
+
+    fn text(state: &State) -> String {
+        state.texteditor.text_without_cursor().to_string()
+    }
+
+    mod motion {
+        use super::{normal, type_keys};
+
+        #[test]
+        fn test() {
+            let (mut vi, mut state) = normal("foo bar.baz qux");
+            type_keys(&mut vi, &mut state, "w");
+            assert_eq!(4, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "2w");
+            assert_eq!(8, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "e");
+            assert_eq!(10, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "b");
+            assert_eq!(8, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "$");
+            assert_eq!(14, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "0fa");
+            assert_eq!(5, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "ta");
+            assert_eq!(8, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "Fo");
+            assert_eq!(2, state.texteditor.position());
+        }
+    }
+
+    mod count {
+        use super::{normal, text, type_keys};
+
+        #[test]
+        fn test() {
+            let (mut vi, mut state) = normal("foo bar");
+            type_keys(&mut vi, &mut state, "99999999999999999999999l");
+            assert_eq!(6, state.texteditor.position());
+            type_keys(&mut vi, &mut state, "0yw99999999999999999999999P");
+            assert_eq!(
+                "foo ".repeat(super::super::MAX_COUNT) + "foo bar",
+                text(&state)
+            );
+            type_keys(&mut vi, &mut state, "u0999999999d999999999w");
+            assert_eq!("", text(&state));
+        }
+    }
+
+    mod operator {
+        use super::{normal, text, type_keys};
+        use crate::text_editor::vi::ViMode;
+
+        #[test]
+        fn test_delete() {
+            let (mut vi, mut state) = normal("one two three four");
+            type_keys(&mut vi, &mut state, "2dw");
+            assert_eq!("three four", text(&state));
+            assert_eq!("one two ", vi.register());
+            type_keys(&mut vi, &mut state, "d$");
+            assert_eq!("", text(&state));
+        }
+
+        #[test]
+        fn test_change() {
+            let (mut vi, mut state) = normal("one two");
+            type_keys(&mut vi, &mut state, "cwsix");
+            assert_eq!(ViMode::Insert, vi.mode());
+            type_keys(&mut vi, &mut state, "⎋");
+            assert_eq!("six two", text(&state));
+            assert_eq!(ViMode::Normal, vi.mode());
+        }
+
+        #[test]
+        fn test_yank_and_paste() {
+            let (mut vi, mut state) = normal("ab cd");
+            type_keys(&mut vi, &mut state, "yw$p");
+            assert_eq!("ab cdab ", text(&state));
+            type_keys(&mut vi, &mut state, "dd");
+            assert_eq!("", text(&state));
+            type_keys(&mut vi, &mut state, "P");
+            assert_eq!("ab cdab ", text(&state));
+        }
+
+        #[test]
+        fn test_with_find() {
+            let (mut vi, mut state) = normal("call(a, b)");
+            type_keys(&mut vi, &mut state, "dt(");
+            assert_eq!("(a, b)", text(&state));
+            type_keys(&mut vi, &mut state, "df,");
+            assert_eq!(" b)", text(&state));
+        }
+    }
+
+    mod repeat {
+        use super::{normal, text, type_keys};
+
+        #[test]
+        fn test() {
+            let (mut vi, mut state) = normal("a b c d e f");
+            type_keys(&mut vi, &mut state, "dw.");
+            assert_eq!("c d e f", text(&state));
+            type_keys(&mut vi, &mut state, "3.");
+            assert_eq!("f", text(&state));
+        }
+
+        #[test]
+        fn test_with_insert() {
+            let (mut vi, mut state) = normal("x y");
+            type_keys(&mut vi, &mut state, "cwfoo⎋w.");
+            assert_eq!("foo foo", text(&state));
+        }
+    }
+
+    mod visual {
+        use super::{normal, text, type_keys};
+        use crate::text_editor::vi::ViMode;
+
+        #[test]
+        fn test() {
+            let (mut vi, mut state) = normal("hello world");
+            type_keys(&mut vi, &mut state, "wve");
+            assert_eq!(ViMode::Visual, vi.mode());
+            type_keys(&mut vi, &mut state, "d");
+            assert_eq!("hello ", text(&state));
+            assert_eq!("world", vi.register());
+            assert_eq!(ViMode::Normal, vi.mode());
+        }
+
+        #[test]
+        fn test_selection() {
+            let (mut vi, mut state) = normal("hello world");
+            type_keys(&mut vi, &mut state, "vll");
+            assert_eq!(Some(0..2), state.texteditor.selection());
+            type_keys(&mut vi, &mut state, "w");
+            assert_eq!(Some(0..6), state.texteditor.selection());
+            type_keys(&mut vi, &mut state, "⎋");
+            assert_eq!(None, state.texteditor.selection());
+        }
+    }
+
+    mod insert_hints {
+        use crate::text_editor::vi;
+
+        #[test]
+        fn test() {
+            assert!(vi::insert_hints(vec![])[0].key == "Esc");
+        }
+    }
+
+    mod show_indicator {
+        use super::{new_state, type_keys};
+        use crate::text_editor::vi::Vi;
+
+        #[test]
+        fn test() {
+            let mut vi = Vi::default();
+            let mut state = new_state("");
+            vi.show_indicator(&mut state);
+            assert_eq!("(ins) ❯ ", state.prefix);
+            type_keys(&mut vi, &mut state, "⎋");
+            assert_eq!("(cmd) ❯ ", state.prefix);
+            vi.hide_indicator(&mut state);
+            assert_eq!("❯ ", state.prefix);
+        }
+    }
+}
@@ -1,48 +1,172 @@
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, HashSet, VecDeque},
     fmt,
     ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, RwLock, RwLockReadGuard,
+    },
 };
 
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthChar;
 
-use crate::crossterm::style::{Attribute, ContentStyle};
+use crate::{
+    crossterm::style::{Attribute, ContentStyle},
+    style::color_depth,
+};
+
+/// Policy for measuring the display width of characters,
+/// shared by the whole process (see [`set_width_policy`]).
+///
+/// It is used wherever widths matter, e.g. by `matrixify`,
+/// the cursor position of the text editor and the cursors of lists.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WidthPolicy {
+    /// Whether East Asian ambiguous-width characters (e.g. `○`, `…` and box drawing)
+    /// occupy two columns, as in many CJK terminal configurations.
+    pub ambiguous_wide: bool,
+    /// Widths of specific characters, taking precedence over everything else.
+    pub overrides: BTreeMap<char, usize>,
+}
+
+static WIDTH_POLICY: RwLock<WidthPolicy> = RwLock::new(WidthPolicy {
+    ambiguous_wide: false,
+    overrides: BTreeMap::new(),
+});
+
+/// Sets the width policy used to measure all graphemes created from now on.
+/// Graphemes are measured once when they are created, so this is meant to be called at startup.
+pub fn set_width_policy(policy: WidthPolicy) {
+    *WIDTH_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
 
-/// Represents a single grapheme (character) with its display width and optional styling.
+/// Returns the width policy currently in use.
+pub fn width_policy() -> WidthPolicy {
+    WIDTH_POLICY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Returns the display width of an extended grapheme cluster.
+///
+/// The cluster occupies the width of its widest character, so that combining marks
+/// and zero width joiners do not add to it. Emoji sequences (flags and those with
+/// the emoji presentation selector U+FE0F) are rendered wide by terminals.
+fn cluster_width(cluster: &str) -> usize {
+    cluster_width_with(cluster, &current_width_policy())
+}
+
+fn current_width_policy() -> RwLockReadGuard<'static, WidthPolicy> {
+    WIDTH_POLICY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn cluster_width_with(cluster: &str, policy: &WidthPolicy) -> usize {
+    let width = cluster
+        .chars()
+        .map(|ch| match policy.overrides.get(&ch) {
+            Some(width) => *width,
+            None if policy.ambiguous_wide => UnicodeWidthChar::width_cjk(ch).unwrap_or(0),
+            None => UnicodeWidthChar::width(ch).unwrap_or(0),
+        })
+        .max()
+        .unwrap_or(0);
+    let is_emoji_sequence = cluster.chars().nth(1).is_some()
+        && cluster
+            .chars()
+            .any(|ch| ch == '\u{FE0F}' || ('\u{1F1E6}'..='\u{1F1FF}').contains(&ch));
+    if is_emoji_sequence {
+        width.max(2)
+    } else {
+        width
+    }
+}
+
+/// Represents a single grapheme (an extended grapheme cluster, i.e. a user-perceived character
+/// such as `é`, `🇯🇵` or `👨‍👩‍👧`) with its display width and optional styling.
 ///
 /// This structure is similar to `Grapheme` but includes styling information directly.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StyledGrapheme {
-    ch: char,
+    cluster: String,
+    /// Display width of the cluster, measured when it is created
+    /// so that layouts do not consult the width policy for every grapheme.
     width: usize,
     style: ContentStyle,
+    /// Target of the hyperlink this grapheme belongs to, if any.
+    link: Option<Arc<str>>,
 }
 
 impl From<char> for StyledGrapheme {
     fn from(ch: char) -> Self {
-        Self {
-            ch,
-            width: UnicodeWidthChar::width(ch).unwrap_or(0),
-            style: ContentStyle::default(),
-        }
+        Self::new(ch, ContentStyle::default())
     }
 }
 
-impl ToString for StyledGrapheme {
-    fn to_string(&self) -> String {
-        self.ch.to_string()
+impl fmt::Display for StyledGrapheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cluster)
     }
 }
 
 impl StyledGrapheme {
     pub fn new(ch: char, style: ContentStyle) -> Self {
+        Self::from_cluster(ch.to_string(), style)
+    }
+
+    /// Creates a `StyledGrapheme` from a string holding a single extended grapheme cluster.
+    pub fn from_cluster<S: AsRef<str>>(cluster: S, style: ContentStyle) -> Self {
+        Self::with_policy(cluster, style, &current_width_policy())
+    }
+
+    fn with_policy<S: AsRef<str>>(cluster: S, style: ContentStyle, policy: &WidthPolicy) -> Self {
+        let cluster = cluster.as_ref().to_string();
         Self {
-            ch,
-            width: UnicodeWidthChar::width(ch).unwrap_or(0),
+            width: cluster_width_with(&cluster, policy),
+            cluster,
             style,
+            link: None,
+        }
+    }
+
+    /// Returns the extended grapheme cluster as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.cluster
+    }
+
+    /// Returns `true` if the cluster consists of whitespace only.
+    pub fn is_whitespace(&self) -> bool {
+        self.cluster.chars().all(char::is_whitespace)
+    }
+
+    /// Returns `true` if the cluster is a single character contained in `chars`.
+    pub fn is_any_of(&self, chars: &HashSet<char>) -> bool {
+        let mut iter = self.cluster.chars();
+        matches!((iter.next(), iter.next()), (Some(ch), None) if chars.contains(&ch))
+    }
+
+    /// Returns `true` if appending `next` to this grapheme forms a single cluster,
+    /// e.g. when `next` is a combining mark or follows a zero width joiner.
+    fn joins(&self, next: &str) -> bool {
+        format!("{}{}", self.cluster, next)
+            .graphemes(true)
+            .nth(1)
+            .is_none()
+    }
+
+    /// Appends `next` to this grapheme if both form a single cluster.
+    /// Returns `false` (leaving this grapheme unchanged) otherwise.
+    pub fn join(&mut self, next: &str) -> bool {
+        if !self.joins(next) {
+            return false;
         }
+        self.cluster.push_str(next);
+        self.width = cluster_width(&self.cluster);
+        true
     }
 
+    /// Returns the display width of the grapheme under the [`WidthPolicy`] in use when it was created.
     pub fn width(&self) -> usize {
         self.width
     }
@@ -50,6 +174,67 @@ impl StyledGrapheme {
     pub fn apply_style(&mut self, style: ContentStyle) {
         self.style = style;
     }
+
+    /// Returns the target of the hyperlink this grapheme belongs to, if any.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// Sets the target of the hyperlink this grapheme belongs to.
+    pub fn apply_link(&mut self, link: Option<Arc<str>>) {
+        self.link = link;
+    }
+}
+
+const HYPERLINKS_AUTO: u8 = 0;
+const HYPERLINKS_ON: u8 = 1;
+const HYPERLINKS_OFF: u8 = 2;
+
+static HYPERLINKS: AtomicU8 = AtomicU8::new(HYPERLINKS_AUTO);
+
+/// Sets whether hyperlinks are emitted as OSC 8 sequences by [`StyledGraphemesDisplay`].
+/// When disabled, only the text of each link is printed.
+///
+/// By default, hyperlinks are emitted unless `TERM` is `dumb` or `linux`
+/// (the Linux console), which print the sequences as garbage.
+pub fn enable_hyperlinks(enabled: bool) {
+    HYPERLINKS.store(
+        if enabled {
+            HYPERLINKS_ON
+        } else {
+            HYPERLINKS_OFF
+        },
+        Ordering::Relaxed,
+    );
+}
+
+/// Returns whether hyperlinks are emitted as OSC 8 sequences.
+pub fn hyperlinks_enabled() -> bool {
+    match HYPERLINKS.load(Ordering::Relaxed) {
+        HYPERLINKS_ON => true,
+        HYPERLINKS_OFF => false,
+        _ => {
+            let enabled = !matches!(std::env::var("TERM").as_deref(), Ok("dumb") | Ok("linux"));
+            enable_hyperlinks(enabled);
+            enabled
+        }
+    }
+}
+
+/// Determines how `StyledGraphemes` wider than the available width are laid out
+/// by [`StyledGraphemes::matrixify_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// Breaks rows at any character.
+    #[default]
+    Char,
+    /// Breaks rows at whitespace,
+    /// falling back to characters for words wider than a row.
+    Word,
+    /// Keeps a single row, replacing the overflow with `…`.
+    Truncate,
+    /// Keeps a single row, showing the part that starts at the given display column.
+    Scroll(usize),
 }
 
 /// A collection of `StyledGrapheme` instances.
@@ -93,6 +278,8 @@ impl<'a> FromIterator<&'a StyledGraphemes> for StyledGraphemes {
 }
 
 impl FromIterator<StyledGrapheme> for StyledGraphemes {
+    /// Collects graphemes as they are, without joining them into clusters
+    /// (see [`StyledGraphemes::push_joined`]).
     fn from_iter<I: IntoIterator<Item = StyledGrapheme>>(iter: I) -> Self {
         let mut g = StyledGraphemes::default();
         for i in iter {
@@ -111,41 +298,66 @@ impl<S: AsRef<str>> From<S> for StyledGraphemes {
 impl fmt::Debug for StyledGraphemes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for styled_grapheme in self.iter() {
-            write!(f, "{}", styled_grapheme.ch)?;
+            write!(f, "{}", styled_grapheme.cluster)?;
         }
         Ok(())
     }
 }
 
+// `Display` is not implemented here because it would conflict with
+// the blanket `FromIterator<T: Display>` implementations (e.g. `Listbox`).
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for StyledGraphemes {
     fn to_string(&self) -> String {
-        self.iter().map(|g| g.ch).collect()
+        self.iter().map(|g| g.as_str()).collect()
     }
 }
 
 impl StyledGraphemes {
     pub fn from_str<S: AsRef<str>>(string: S, style: ContentStyle) -> Self {
+        let policy = current_width_policy();
         string
             .as_ref()
-            .chars()
-            .map(|ch| StyledGrapheme::new(ch, style))
+            .graphemes(true)
+            .map(|cluster| StyledGrapheme::with_policy(cluster, style, &policy))
             .collect()
     }
 
+    /// Appends `grapheme` to the end of the collection,
+    /// joining it to the last grapheme if both form a single extended grapheme cluster
+    /// (e.g. a base character followed by a combining mark).
+    /// This is used when text arrives one character at a time.
+    pub fn push_joined(&mut self, grapheme: StyledGrapheme) {
+        if let Some(last) = self.0.back_mut() {
+            if last.style == grapheme.style
+                && last.link == grapheme.link
+                && last.join(&grapheme.cluster)
+            {
+                return;
+            }
+        }
+        self.0.push_back(grapheme);
+    }
+
     /// Returns a `Vec<char>` containing the characters of all `Grapheme` instances in the collection.
+    /// Note that a grapheme may consist of several characters,
+    /// so the indices do not correspond to those of the graphemes.
     pub fn chars(&self) -> Vec<char> {
-        self.0.iter().map(|grapheme| grapheme.ch).collect()
+        self.0
+            .iter()
+            .flat_map(|grapheme| grapheme.cluster.chars())
+            .collect()
     }
 
     /// Calculates the total display width of all `Grapheme` instances in the collection.
     pub fn widths(&self) -> usize {
-        self.0.iter().map(|grapheme| grapheme.width).sum()
+        self.0.iter().map(|grapheme| grapheme.width()).sum()
     }
 
     /// Replaces all occurrences of a substring `from` with another substring `to` within the `StyledGraphemes`.
     pub fn replace<S: AsRef<str>>(mut self, from: S, to: S) -> Self {
-        let from_len = from.as_ref().chars().count();
-        let to_len = to.as_ref().chars().count();
+        let from_len = from.as_ref().graphemes(true).count();
+        let to_len = to.as_ref().graphemes(true).count();
 
         let mut offset = 0;
         let diff = from_len.abs_diff(to_len);
@@ -204,14 +416,14 @@ impl StyledGraphemes {
 
         let mut indices = Vec::new();
         let mut pos = 0;
-        let query_chars: Vec<char> = query_str.chars().collect();
-        let query_len = query_chars.len();
+        let query_clusters: Vec<&str> = query_str.graphemes(true).collect();
+        let query_len = query_clusters.len();
 
         // Iterate through each grapheme in self
         while pos + query_len <= self.0.len() {
             let mut match_found = true;
-            for (i, query_char) in query_chars.iter().enumerate() {
-                if self.0[pos + i].ch != *query_char {
+            for (i, query_cluster) in query_clusters.iter().enumerate() {
+                if self.0[pos + i].cluster != *query_cluster {
                     match_found = false;
                     break;
                 }
@@ -236,7 +448,6 @@ impl StyledGraphemes {
     ///     - with the style applied to all occurrences of the query if the query is found.
     ///     - unchanged if the query string is empty.
     /// - `None`: if the query string is not found in the collection.
-
     pub fn highlight<S: AsRef<str>>(mut self, query: S, style: ContentStyle) -> Option<Self> {
         let query_str = query.as_ref();
         if query_str.is_empty() {
@@ -248,7 +459,7 @@ impl StyledGraphemes {
             return None;
         }
 
-        let query_len = query_str.chars().count();
+        let query_len = query_str.graphemes(true).count();
 
         for &start_index in &indices {
             for i in start_index..start_index + query_len {
@@ -261,6 +472,16 @@ impl StyledGraphemes {
         Some(self)
     }
 
+    /// Turns all `StyledGrapheme` instances within the collection into a hyperlink to `url`.
+    /// The target is not part of the display width.
+    pub fn apply_link<S: AsRef<str>>(mut self, url: S) -> Self {
+        let link: Arc<str> = Arc::from(url.as_ref());
+        for styled_grapheme in &mut self.0 {
+            styled_grapheme.link = Some(link.clone());
+        }
+        self
+    }
+
     /// Applies a given attribute to all `StyledGrapheme` instances within the collection.
     pub fn apply_attribute(mut self, attr: Attribute) -> Self {
         for styled_grapheme in &mut self.0 {
@@ -269,39 +490,186 @@ impl StyledGraphemes {
         self
     }
 
+    /// Draws `other` on top of these graphemes, starting at the display column `column`.
+    /// The graphemes underneath are replaced, the row is padded with spaces if it is
+    /// shorter than `column`, and wide characters cut by either edge become spaces.
+    pub fn overlay(&self, other: &StyledGraphemes, column: usize) -> Self {
+        let end = column + other.widths();
+        let mut left = StyledGraphemes::default();
+        let mut right = StyledGraphemes::default();
+        let mut pos = 0;
+        for styled in self.iter() {
+            if pos + styled.width() <= column {
+                left.push_back(styled.clone());
+            } else if pos >= end {
+                right.push_back(styled.clone());
+            } else if end < pos + styled.width() {
+                for _ in 0..pos + styled.width() - end {
+                    right.push_back(StyledGrapheme::new(' ', styled.style));
+                }
+            }
+            pos += styled.width();
+        }
+        let padding = column - left.widths();
+        StyledGraphemes::from_iter([
+            &left,
+            &StyledGraphemes::from(" ".repeat(padding)),
+            other,
+            &right,
+        ])
+    }
+
     /// Returns a displayable format of the styled graphemes.
     pub fn styled_display(&self) -> StyledGraphemesDisplay<'_> {
         StyledGraphemesDisplay {
             styled_graphemes: self,
+            hyperlinks: None,
         }
     }
 
-    /// Organizes the `StyledGraphemes` into a matrix format based on specified width and height,
-    /// considering an offset for pagination or scrolling.
-    pub fn matrixify(
-        &self,
-        width: usize,
-        height: usize,
-        offset: usize,
-    ) -> (Vec<StyledGraphemes>, usize) {
+    fn wrap_chars(&self, width: usize) -> VecDeque<StyledGraphemes> {
         let mut all = VecDeque::new();
         let mut row = StyledGraphemes::default();
         for styled in self.iter() {
             let width_with_next_char = row.iter().fold(0, |mut layout, g| {
-                layout += g.width;
+                layout += g.width();
                 layout
-            }) + styled.width;
+            }) + styled.width();
             if !row.is_empty() && width < width_with_next_char {
                 all.push_back(row);
                 row = StyledGraphemes::default();
             }
-            if width >= styled.width {
+            if width >= styled.width() {
                 row.push_back(styled.clone());
             }
         }
         if !row.is_empty() {
             all.push_back(row);
         }
+        all
+    }
+
+    fn wrap_words(&self, width: usize) -> VecDeque<StyledGraphemes> {
+        let mut all = VecDeque::new();
+        let mut row = StyledGraphemes::default();
+        let mut word = StyledGraphemes::default();
+        // Ends the row at a wrap point, dropping its trailing whitespace.
+        // A row of indentation alone is dropped rather than left empty.
+        let wrap = |all: &mut VecDeque<StyledGraphemes>, row: &mut StyledGraphemes| {
+            let ended = std::mem::take(row).trim_end();
+            if !ended.is_empty() {
+                all.push_back(ended);
+            }
+        };
+        // A trailing whitespace flushes the last word.
+        for styled in self.iter().chain([&StyledGrapheme::from(' ')]) {
+            if !styled.is_whitespace() {
+                word.push_back(styled.clone());
+                continue;
+            }
+            if !word.is_empty() {
+                if !row.is_empty() && row.widths() + word.widths() > width {
+                    wrap(&mut all, &mut row);
+                }
+                if word.widths() > width {
+                    // Words wider than a row are broken at characters.
+                    let mut rows = word.wrap_chars(width);
+                    row = rows.pop_back().unwrap_or_default();
+                    all.extend(rows);
+                } else {
+                    row.append(&mut word);
+                }
+                word = StyledGraphemes::default();
+            }
+            if row.widths() + styled.width() > width {
+                wrap(&mut all, &mut row);
+            } else if !row.is_empty() || all.is_empty() {
+                // Whitespace is dropped at the start of wrapped rows only,
+                // so the indentation of the line is kept.
+                row.push_back(styled.clone());
+            }
+        }
+        let row = row.trim_end();
+        if !row.is_empty() {
+            all.push_back(row);
+        }
+        all
+    }
+
+    fn truncate(&self, width: usize) -> VecDeque<StyledGraphemes> {
+        if self.is_empty() {
+            return VecDeque::new();
+        }
+        if self.widths() <= width {
+            return VecDeque::from([self.clone()]);
+        }
+        let mut row = StyledGraphemes::default();
+        // Not even the ellipsis fits, so the row is left empty.
+        if StyledGrapheme::from('…').width() > width {
+            return VecDeque::from([row]);
+        }
+        for styled in self.iter() {
+            let ellipsis = StyledGrapheme::new('…', styled.style);
+            if row.widths() + styled.width() + ellipsis.width() > width {
+                row.push_back(ellipsis);
+                break;
+            }
+            row.push_back(styled.clone());
+        }
+        VecDeque::from([row])
+    }
+
+    fn scroll(&self, width: usize, column: usize) -> VecDeque<StyledGraphemes> {
+        if self.is_empty() {
+            return VecDeque::new();
+        }
+        let mut row = StyledGraphemes::default();
+        let mut pos = 0;
+        for styled in self.iter() {
+            if pos >= column {
+                if row.widths() + styled.width() > width {
+                    break;
+                }
+                row.push_back(styled.clone());
+            }
+            pos += styled.width();
+        }
+        VecDeque::from([row])
+    }
+
+    fn trim_end(mut self) -> Self {
+        while self.back().is_some_and(|g| g.is_whitespace()) {
+            self.pop_back();
+        }
+        self
+    }
+
+    /// Organizes the `StyledGraphemes` into a matrix format based on specified width and height,
+    /// considering an offset for pagination or scrolling.
+    /// Rows are broken at any character; see [`Self::matrixify_with`] for other modes.
+    pub fn matrixify(
+        &self,
+        width: usize,
+        height: usize,
+        offset: usize,
+    ) -> (Vec<StyledGraphemes>, usize) {
+        self.matrixify_with(Wrap::Char, width, height, offset)
+    }
+
+    /// Same as [`Self::matrixify`], but lays out rows wider than `width` according to `wrap`.
+    pub fn matrixify_with(
+        &self,
+        wrap: Wrap,
+        width: usize,
+        height: usize,
+        offset: usize,
+    ) -> (Vec<StyledGraphemes>, usize) {
+        let mut all = match wrap {
+            Wrap::Char => self.wrap_chars(width),
+            Wrap::Word => self.wrap_words(width),
+            Wrap::Truncate => self.truncate(width),
+            Wrap::Scroll(column) => self.scroll(width, column),
+        };
 
         if all.is_empty() {
             return (vec![], 0);
@@ -325,12 +693,45 @@ impl StyledGraphemes {
 
 pub struct StyledGraphemesDisplay<'a> {
     styled_graphemes: &'a StyledGraphemes,
+    /// Whether hyperlinks are emitted, which follows [`hyperlinks_enabled`] if `None`.
+    hyperlinks: Option<bool>,
+}
+
+impl StyledGraphemesDisplay<'_> {
+    /// Sets whether hyperlinks are emitted as OSC 8 sequences,
+    /// regardless of the process-wide setting (see [`enable_hyperlinks`]).
+    pub fn hyperlinks(mut self, enabled: bool) -> Self {
+        self.hyperlinks = Some(enabled);
+        self
+    }
 }
 
 impl<'a> fmt::Display for StyledGraphemesDisplay<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hyperlinks = self.hyperlinks.unwrap_or_else(hyperlinks_enabled);
+        let depth = color_depth();
+        let mut current: Option<&str> = None;
         for styled_grapheme in self.styled_graphemes.iter() {
-            write!(f, "{}", styled_grapheme.style.apply(styled_grapheme.ch))?;
+            let link = styled_grapheme.link().filter(|_| hyperlinks);
+            if link != current {
+                if current.is_some() {
+                    write!(f, "\x1b]8;;\x1b\\")?;
+                }
+                if let Some(url) = link {
+                    write!(f, "\x1b]8;;{}\x1b\\", url)?;
+                }
+                current = link;
+            }
+            write!(
+                f,
+                "{}",
+                depth
+                    .downsample(styled_grapheme.style)
+                    .apply(&styled_grapheme.cluster)
+            )?;
+        }
+        if current.is_some() {
+            write!(f, "\x1b]8;;\x1b\\")?;
         }
         Ok(())
     }
@@ -346,7 +747,7 @@ mod test {
         #[test]
         fn test() {
             let style = ContentStyle::default();
-            let graphemes = StyledGraphemes::from_str("abc", style.clone());
+            let graphemes = StyledGraphemes::from_str("abc", style);
             assert_eq!(3, graphemes.0.len());
             assert!(graphemes.0.iter().all(|g| g.style == style));
         }
@@ -371,6 +772,44 @@ mod test {
             let graphemes = StyledGraphemes::from("a b");
             assert_eq!(3, graphemes.widths()); // 'a' and 'b' are each 1 width, and space is 1 width
         }
+
+        #[test]
+        fn test_with_clusters() {
+            for (text, len, widths) in [
+                ("e\u{301}", 1, 1),
+                ("🇯🇵", 1, 2),
+                ("👨\u{200D}👩\u{200D}👧", 1, 2),
+                ("❤\u{FE0F}", 1, 2),
+                ("あa", 2, 3),
+            ] {
+                let graphemes = StyledGraphemes::from(text);
+                assert_eq!(len, graphemes.len(), "{}", text);
+                assert_eq!(widths, graphemes.widths(), "{}", text);
+            }
+        }
+    }
+
+    mod cluster_width_with {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let policy = WidthPolicy::default();
+            assert_eq!(1, cluster_width_with("○", &policy));
+            assert_eq!(1, cluster_width_with("…", &policy));
+        }
+
+        #[test]
+        fn test_with_ambiguous_wide() {
+            let policy = WidthPolicy {
+                ambiguous_wide: true,
+                overrides: BTreeMap::from([('a', 2)]),
+            };
+            assert_eq!(2, cluster_width_with("○", &policy));
+            assert_eq!(2, cluster_width_with("…", &policy));
+            assert_eq!(2, cluster_width_with("a", &policy));
+            assert_eq!(1, cluster_width_with("b", &policy));
+        }
     }
 
     mod replace_char {
@@ -412,6 +851,92 @@ mod test {
         }
     }
 
+    mod matrixify_with {
+        use super::*;
+
+        fn rows(wrap: Wrap, s: &str, width: usize) -> Vec<String> {
+            StyledGraphemes::from(s)
+                .matrixify_with(wrap, width, 10, 0)
+                .0
+                .iter()
+                .map(|row| row.to_string())
+                .collect()
+        }
+
+        #[test]
+        fn test_char() {
+            assert_eq!(vec!["hello wo", "rld"], rows(Wrap::Char, "hello world", 8));
+        }
+
+        #[test]
+        fn test_word() {
+            assert_eq!(
+                vec!["hello", "world foo"],
+                rows(Wrap::Word, "hello world foo", 9)
+            );
+        }
+
+        #[test]
+        fn test_word_with_long_word() {
+            assert_eq!(
+                vec!["a", "abcdef", "gh b"],
+                rows(Wrap::Word, "a abcdefgh b", 6)
+            );
+        }
+
+        #[test]
+        fn test_word_with_indentation() {
+            assert_eq!(
+                vec!["  indented", "line"],
+                rows(Wrap::Word, "  indented line", 10)
+            );
+        }
+
+        #[test]
+        fn test_truncate() {
+            assert_eq!(vec!["hello w…"], rows(Wrap::Truncate, "hello world", 8));
+            assert_eq!(vec!["hello"], rows(Wrap::Truncate, "hello", 8));
+            assert_eq!(vec!["…"], rows(Wrap::Truncate, "hello", 1));
+            assert_eq!(vec![""], rows(Wrap::Truncate, "hello", 0));
+        }
+
+        #[test]
+        fn test_scroll() {
+            assert_eq!(vec!["lo wor"], rows(Wrap::Scroll(3), "hello world", 6));
+        }
+    }
+
+    mod overlay {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let base = StyledGraphemes::from("abcdef");
+            assert_eq!(
+                "abXYef",
+                base.overlay(&StyledGraphemes::from("XY"), 2).to_string()
+            );
+        }
+
+        #[test]
+        fn test_beyond_end() {
+            let base = StyledGraphemes::from("ab");
+            assert_eq!(
+                "ab  XY",
+                base.overlay(&StyledGraphemes::from("XY"), 4).to_string()
+            );
+        }
+
+        #[test]
+        fn test_with_wide_characters() {
+            let base = StyledGraphemes::from("あいう");
+            assert_eq!(
+                "あ XY ",
+                base.overlay(&StyledGraphemes::from("XY"), 3).to_string()
+            );
+        }
+    }
+
     mod apply_style {
         use crate::{crossterm::style::Color, style::StyleBuilder};
 
@@ -421,7 +946,7 @@ mod test {
         fn test() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style(new_style.clone());
+            graphemes = graphemes.apply_style(new_style);
             assert!(graphemes.iter().all(|g| g.style == new_style));
         }
     }
@@ -435,7 +960,7 @@ mod test {
         fn test_apply_style_at_specific_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style_at(1, new_style.clone());
+            graphemes = graphemes.apply_style_at(1, new_style);
             assert_eq!(graphemes.0[1].style, new_style);
             assert_ne!(graphemes.0[0].style, new_style);
             assert_ne!(graphemes.0[2].style, new_style);
@@ -445,7 +970,7 @@ mod test {
         fn test_apply_style_at_out_of_bounds_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style_at(5, new_style.clone()); // Out of bounds
+            graphemes = graphemes.apply_style_at(5, new_style); // Out of bounds
             assert_eq!(graphemes.0.len(), 3); // Ensure no changes in length
         }
     }
@@ -559,6 +1084,23 @@ mod test {
             let display = graphemes.styled_display();
             assert_eq!(format!("{}", display), "abc"); // Assuming default styles do not alter appearance
         }
+
+        #[test]
+        fn test_with_link() {
+            let graphemes = StyledGraphemes::from_iter([
+                StyledGraphemes::from("see "),
+                StyledGraphemes::from("docs").apply_link("https://example.com"),
+            ]);
+            assert_eq!(8, graphemes.widths());
+            assert_eq!(
+                "see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\",
+                graphemes.styled_display().hyperlinks(true).to_string()
+            );
+            assert_eq!(
+                "see docs",
+                graphemes.styled_display().hyperlinks(false).to_string()
+            );
+        }
     }
 
     #[cfg(test)]
@@ -127,16 +127,21 @@ pub use serde_json;
 
 mod core;
 pub use core::*;
+pub mod ansi;
+pub mod binding;
 pub mod grapheme;
+pub mod locale;
+pub mod markup;
 pub mod pane;
 pub mod preset;
 pub mod style;
 pub mod suggest;
 pub mod switch;
 pub mod terminal;
+pub mod theme;
 pub mod validate;
 
-use std::io;
+use std::{io, time::Duration};
 
 use crate::{
     crossterm::{
@@ -146,7 +151,7 @@ use crate::{
         terminal::{disable_raw_mode, enable_raw_mode},
     },
     pane::Pane,
-    terminal::Terminal,
+    terminal::{PlainTerminal, Terminal},
 };
 
 /// Represents the signal to control the flow of a prompt.
@@ -215,6 +220,22 @@ pub trait Renderer: Finalizer {
     /// that the prompt should continue running, while `PromptSignal::Quit` indicates that
     /// the prompt should terminate its execution.
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal>;
+
+    /// Returns how long to wait for the next event before calling [`Self::tick`],
+    /// or `None` to wait for it indefinitely, which is the default.
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Takes the text copied since the last call,
+    /// which the prompt sends to the system clipboard (see [`crate::text_editor::Clipboard`]).
+    fn take_copied_text(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Updates the state when no event has arrived within [`Self::tick_interval`],
+    /// e.g. to hide the keys of a chord that timed out. The prompt is redrawn afterwards.
+    fn tick(&mut self) {}
 }
 
 /// Represents a customizable prompt that can handle user input and produce a result.
@@ -249,6 +270,10 @@ impl<T: Renderer> Prompt<T> {
     ///
     /// Returns a `Result` containing the produced result or an error.
     pub fn run(&mut self) -> anyhow::Result<T::Return> {
+        if terminal::accessible_mode_enabled() {
+            return self.run_plain();
+        }
+
         enable_raw_mode()?;
         execute!(io::stdout(), cursor::Hide)?;
 
@@ -258,6 +283,14 @@ impl<T: Renderer> Prompt<T> {
         terminal.draw(&panes)?;
 
         loop {
+            if let Some(interval) = self.renderer.tick_interval() {
+                if !event::poll(interval)? {
+                    self.renderer.tick();
+                    let size = crossterm::terminal::size()?;
+                    terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+                    continue;
+                }
+            }
             let ev = event::read()?;
 
             match &ev {
@@ -269,7 +302,11 @@ impl<T: Renderer> Prompt<T> {
                     )?;
                 }
                 _ => {
-                    if self.renderer.evaluate(&ev)? == PromptSignal::Quit {
+                    let signal = self.renderer.evaluate(&ev)?;
+                    if let Some(text) = self.renderer.take_copied_text() {
+                        terminal.copy_to_clipboard(&text)?;
+                    }
+                    if signal == PromptSignal::Quit {
                         break;
                     }
                 }
@@ -281,4 +318,41 @@ impl<T: Renderer> Prompt<T> {
 
         self.renderer.finalize()
     }
+
+    /// Runs the prompt in accessible mode (see [`terminal::enable_accessible_mode`]),
+    /// where changes are printed as plain lines instead of redrawing the prompt area.
+    fn run_plain(&mut self) -> anyhow::Result<T::Return> {
+        enable_raw_mode()?;
+
+        let size = crossterm::terminal::size()?;
+        let mut terminal = PlainTerminal::default();
+        terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+
+        loop {
+            if let Some(interval) = self.renderer.tick_interval() {
+                if !event::poll(interval)? {
+                    self.renderer.tick();
+                    let size = crossterm::terminal::size()?;
+                    terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+                    continue;
+                }
+            }
+            let ev = event::read()?;
+            if matches!(ev, Event::Resize(_, _)) {
+                continue;
+            }
+            let signal = self.renderer.evaluate(&ev)?;
+            if let Some(text) = self.renderer.take_copied_text() {
+                terminal.copy_to_clipboard(&text)?;
+            }
+            if signal == PromptSignal::Quit {
+                break;
+            }
+
+            let size = crossterm::terminal::size()?;
+            terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+        }
+
+        self.renderer.finalize()
+    }
 }
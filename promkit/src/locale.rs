@@ -0,0 +1,337 @@
+//! A replaceable catalog of the user-facing text built into promkit,
+//! such as the `(y/n)` suffix of [`crate::preset::confirm::Confirm`],
+//! the descriptions of key hints and the default cursors and marks.
+//!
+//! Presets read the catalog when they are constructed,
+//! so [`set_messages`] should be called before building prompts:
+//!
+//! ```
+//! use promkit::locale::{self, Messages};
+//!
+//! locale::set_messages(Messages::german());
+//! assert_eq!(Some(true), locale::messages().answer("ja"));
+//! ```
+
+use std::{collections::BTreeMap, sync::RwLock};
+
+use serde::Deserialize;
+
+use crate::binding::Action;
+
+/// The user-facing text built into promkit.
+///
+/// Omitted fields are taken from [`Messages::default`] when deserialized,
+/// so a catalog can be loaded from a configuration file with serde.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Messages {
+    /// Shown instead of the prompt when the terminal is too small.
+    pub insufficient_space: String,
+    /// Title of the help overlay.
+    pub help_title: String,
+
+    /// Appended to the question of confirmation prompts.
+    pub confirm_suffix: String,
+    /// Answers accepted as "yes" by confirmation prompts.
+    pub confirm_yes: Vec<String>,
+    /// Answers accepted as "no" by confirmation prompts.
+    pub confirm_no: Vec<String>,
+    /// Error message shown when the answer to a confirmation prompt is neither yes nor no.
+    pub confirm_error: String,
+
+    /// The prompt string displayed before the input text.
+    pub prefix: String,
+    /// The cursor of lists, checkboxes and trees.
+    pub cursor: String,
+    /// The mark of checked items of checkboxes.
+    pub checked_mark: char,
+    /// The mark of unchecked items of checkboxes.
+    pub unchecked_mark: char,
+    /// The symbol of folded nodes of trees.
+    pub folded_symbol: String,
+    /// The symbol of unfolded nodes of trees.
+    pub unfolded_symbol: String,
+    /// The character that hides the input of password prompts.
+    pub password_mask: char,
+    /// Shown before the prefix in the insert mode of vi editing.
+    pub vi_insert_indicator: String,
+    /// Shown before the prefix in the normal mode of vi editing.
+    pub vi_normal_indicator: String,
+    /// Shown before the prefix in the visual mode of vi editing.
+    pub vi_visual_indicator: String,
+
+    /// Descriptions of the actions shown in key hints,
+    /// keyed by their names in config files (see [`Action::name`]).
+    /// Keys bound to actions with the same description are shown together, e.g. `↑/↓ move`.
+    pub actions: BTreeMap<String, String>,
+
+    /// Key hint for accepting a suggestion.
+    pub hint_accept: String,
+    /// Key hint for entering the insert mode of vi editing.
+    pub hint_vi_insert: String,
+    /// Key hint for returning to the normal mode of vi editing.
+    pub hint_vi_normal: String,
+    /// Key hint for entering the visual mode of vi editing.
+    pub hint_vi_visual: String,
+    /// Key hint for the delete, change and yank operators of vi editing.
+    pub hint_vi_operators: String,
+    /// Key hint for repeating the last change.
+    pub hint_repeat: String,
+}
+
+impl Default for Messages {
+    /// The English text promkit has always used.
+    fn default() -> Self {
+        Self {
+            insufficient_space: String::from("⚠️ Insufficient Space"),
+            help_title: String::from("Key bindings"),
+            confirm_suffix: String::from("(y/n)"),
+            confirm_yes: strings(&["yes", "y", "Y"]),
+            confirm_no: strings(&["no", "n", "N"]),
+            confirm_error: String::from("Please type 'y' or 'n' as an answer"),
+            prefix: String::from("❯❯ "),
+            cursor: String::from("❯ "),
+            checked_mark: '☒',
+            unchecked_mark: '☐',
+            folded_symbol: String::from("▶︎ "),
+            unfolded_symbol: String::from("▼ "),
+            password_mask: '*',
+            vi_insert_indicator: String::from("(ins) "),
+            vi_normal_indicator: String::from("(cmd) "),
+            vi_visual_indicator: String::from("(vis) "),
+            actions: descriptions(&[
+                (Action::Submit, "submit"),
+                (Action::Interrupt, "quit"),
+                (Action::MoveBackward, "move cursor"),
+                (Action::MoveForward, "move cursor"),
+                (Action::MoveToHead, "head/tail"),
+                (Action::MoveToTail, "head/tail"),
+                (Action::MoveWordBackward, "move word"),
+                (Action::MoveWordForward, "move word"),
+                (Action::EraseBackward, "erase"),
+                (Action::EraseForward, "erase"),
+                (Action::EraseAll, "erase all"),
+                (Action::EraseToHead, "erase to head"),
+                (Action::EraseToTail, "erase to tail"),
+                (Action::EraseWordBackward, "erase word backward"),
+                (Action::EraseWordForward, "erase word forward"),
+                (Action::Yank, "paste erased"),
+                (Action::YankPop, "cycle erased"),
+                (Action::Undo, "undo"),
+                (Action::Redo, "redo"),
+                (Action::SelectBackward, "select text"),
+                (Action::SelectForward, "select text"),
+                (Action::SelectToHead, "select to head/tail"),
+                (Action::SelectToTail, "select to head/tail"),
+                (Action::Copy, "copy"),
+                (Action::Cut, "cut"),
+                (Action::Paste, "paste"),
+                (Action::InsertNewline, "new line"),
+                (Action::MoveLineUp, "move line"),
+                (Action::MoveLineDown, "move line"),
+                (Action::HistoryPrevious, "history"),
+                (Action::HistoryNext, "history"),
+                (Action::Complete, "complete"),
+                (Action::SuggestNext, "next"),
+                (Action::SuggestPrevious, "previous"),
+                (Action::MoveUp, "move"),
+                (Action::MoveDown, "move"),
+                (Action::Toggle, "toggle"),
+            ]),
+            hint_accept: String::from("accept"),
+            hint_vi_insert: String::from("insert"),
+            hint_vi_normal: String::from("normal mode"),
+            hint_vi_visual: String::from("visual"),
+            hint_vi_operators: String::from("delete/change/yank"),
+            hint_repeat: String::from("repeat"),
+        }
+    }
+}
+
+impl Messages {
+    /// Japanese text. `y` and `n` are accepted in addition to `はい` and `いいえ`.
+    pub fn japanese() -> Self {
+        Self {
+            insufficient_space: String::from("⚠️ 表示領域が足りません"),
+            help_title: String::from("キー操作"),
+            confirm_suffix: String::from("(はい/いいえ)"),
+            confirm_yes: strings(&["はい", "yes", "y", "Y"]),
+            confirm_no: strings(&["いいえ", "no", "n", "N"]),
+            confirm_error: String::from("「はい」か「いいえ」で答えてください"),
+            actions: descriptions(&[
+                (Action::Submit, "確定"),
+                (Action::Interrupt, "終了"),
+                (Action::MoveBackward, "カーソル移動"),
+                (Action::MoveForward, "カーソル移動"),
+                (Action::MoveToHead, "先頭/末尾"),
+                (Action::MoveToTail, "先頭/末尾"),
+                (Action::MoveWordBackward, "単語移動"),
+                (Action::MoveWordForward, "単語移動"),
+                (Action::EraseBackward, "削除"),
+                (Action::EraseForward, "削除"),
+                (Action::EraseAll, "全て削除"),
+                (Action::EraseToHead, "先頭まで削除"),
+                (Action::EraseToTail, "末尾まで削除"),
+                (Action::EraseWordBackward, "前の単語を削除"),
+                (Action::EraseWordForward, "次の単語を削除"),
+                (Action::Yank, "削除した文字を貼り付け"),
+                (Action::YankPop, "削除した文字を切り替え"),
+                (Action::Undo, "元に戻す"),
+                (Action::Redo, "やり直し"),
+                (Action::SelectBackward, "文字の選択"),
+                (Action::SelectForward, "文字の選択"),
+                (Action::SelectToHead, "先頭/末尾まで選択"),
+                (Action::SelectToTail, "先頭/末尾まで選択"),
+                (Action::Copy, "コピー"),
+                (Action::Cut, "切り取り"),
+                (Action::Paste, "貼り付け"),
+                (Action::InsertNewline, "改行"),
+                (Action::MoveLineUp, "行移動"),
+                (Action::MoveLineDown, "行移動"),
+                (Action::HistoryPrevious, "履歴"),
+                (Action::HistoryNext, "履歴"),
+                (Action::Complete, "補完"),
+                (Action::SuggestNext, "次へ"),
+                (Action::SuggestPrevious, "前へ"),
+                (Action::MoveUp, "移動"),
+                (Action::MoveDown, "移動"),
+                (Action::Toggle, "切り替え"),
+            ]),
+            hint_accept: String::from("採用"),
+            hint_vi_insert: String::from("挿入"),
+            hint_vi_normal: String::from("ノーマルモード"),
+            hint_vi_visual: String::from("ビジュアル"),
+            hint_vi_operators: String::from("削除/変更/ヤンク"),
+            hint_repeat: String::from("繰り返し"),
+            ..Self::default()
+        }
+    }
+
+    /// German text.
+    pub fn german() -> Self {
+        Self {
+            insufficient_space: String::from("⚠️ Nicht genügend Platz"),
+            help_title: String::from("Tastenbelegung"),
+            confirm_suffix: String::from("(j/n)"),
+            confirm_yes: strings(&["ja", "j", "J"]),
+            confirm_no: strings(&["nein", "n", "N"]),
+            confirm_error: String::from("Bitte mit 'j' oder 'n' antworten"),
+            actions: descriptions(&[
+                (Action::Submit, "bestätigen"),
+                (Action::Interrupt, "beenden"),
+                (Action::MoveBackward, "Cursor bewegen"),
+                (Action::MoveForward, "Cursor bewegen"),
+                (Action::MoveToHead, "Anfang/Ende"),
+                (Action::MoveToTail, "Anfang/Ende"),
+                (Action::MoveWordBackward, "wortweise bewegen"),
+                (Action::MoveWordForward, "wortweise bewegen"),
+                (Action::EraseBackward, "löschen"),
+                (Action::EraseForward, "löschen"),
+                (Action::EraseAll, "alles löschen"),
+                (Action::EraseToHead, "bis Anfang löschen"),
+                (Action::EraseToTail, "bis Ende löschen"),
+                (Action::EraseWordBackward, "Wort davor löschen"),
+                (Action::EraseWordForward, "Wort danach löschen"),
+                (Action::Yank, "Gelöschtes einfügen"),
+                (Action::YankPop, "Gelöschtes durchblättern"),
+                (Action::Undo, "rückgängig"),
+                (Action::Redo, "wiederholen"),
+                (Action::SelectBackward, "Text auswählen"),
+                (Action::SelectForward, "Text auswählen"),
+                (Action::SelectToHead, "bis Anfang/Ende auswählen"),
+                (Action::SelectToTail, "bis Anfang/Ende auswählen"),
+                (Action::Copy, "kopieren"),
+                (Action::Cut, "ausschneiden"),
+                (Action::Paste, "einsetzen"),
+                (Action::InsertNewline, "neue Zeile"),
+                (Action::MoveLineUp, "zeilenweise bewegen"),
+                (Action::MoveLineDown, "zeilenweise bewegen"),
+                (Action::HistoryPrevious, "Verlauf"),
+                (Action::HistoryNext, "Verlauf"),
+                (Action::Complete, "vervollständigen"),
+                (Action::SuggestNext, "nächster"),
+                (Action::SuggestPrevious, "vorheriger"),
+                (Action::MoveUp, "bewegen"),
+                (Action::MoveDown, "bewegen"),
+                (Action::Toggle, "umschalten"),
+            ]),
+            hint_accept: String::from("übernehmen"),
+            hint_vi_insert: String::from("einfügen"),
+            hint_vi_normal: String::from("Normalmodus"),
+            hint_vi_visual: String::from("markieren"),
+            hint_vi_operators: String::from("löschen/ändern/kopieren"),
+            hint_repeat: String::from("wiederholen"),
+            ..Self::default()
+        }
+    }
+
+    /// Interprets the answer to a confirmation prompt,
+    /// returning `Some(true)` for yes, `Some(false)` for no and `None` otherwise.
+    /// The answer must match exactly, e.g. `" y "` is not yes.
+    pub fn answer(&self, text: &str) -> Option<bool> {
+        if self.confirm_yes.iter().any(|yes| yes == text) {
+            Some(true)
+        } else if self.confirm_no.iter().any(|no| no == text) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the description of `action` shown in key hints,
+    /// or its name with spaces if [`Self::actions`] has none.
+    pub fn action(&self, action: Action) -> String {
+        self.actions
+            .get(action.name())
+            .cloned()
+            .unwrap_or_else(|| action.name().replace('_', " "))
+    }
+}
+
+fn descriptions(actions: &[(Action, &str)]) -> BTreeMap<String, String> {
+    actions
+        .iter()
+        .map(|(action, description)| (action.name().to_string(), description.to_string()))
+        .collect()
+}
+
+fn strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+static MESSAGES: RwLock<Option<Messages>> = RwLock::new(None);
+
+/// Sets the catalog used by all prompts constructed from now on.
+pub fn set_messages(messages: Messages) {
+    *MESSAGES.write().unwrap_or_else(|e| e.into_inner()) = Some(messages);
+}
+
+/// Returns the catalog in use,
+/// which is [`Messages::default`] unless changed with [`set_messages`].
+pub fn messages() -> Messages {
+    MESSAGES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    mod answer {
+        use crate::locale::Messages;
+
+        #[test]
+        fn test() {
+            let messages = Messages::default();
+            assert_eq!(Some(true), messages.answer("y"));
+            assert_eq!(Some(false), messages.answer("no"));
+            assert_eq!(None, messages.answer(" y "));
+            assert_eq!(None, messages.answer("ja"));
+
+            let messages = Messages::japanese();
+            assert_eq!(Some(true), messages.answer("はい"));
+            assert_eq!(Some(false), messages.answer("n"));
+        }
+    }
+}
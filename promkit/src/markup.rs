@@ -0,0 +1,176 @@
+//! A small markup format for building `StyledGraphemes` with mixed styles.
+//!
+//! A tag such as `[bold]` or `[red on white]` applies a style
+//! until the matching `[/]`, and tags can be nested:
+//!
+//! ```
+//! use promkit::{crossterm::style::ContentStyle, markup};
+//!
+//! let styled = markup::parse("Open [bold]README.md[/] in [dark_cyan italic]vim[/]?", ContentStyle::default());
+//! assert_eq!("Open README.md in vim?", styled.to_string());
+//! ```
+//!
+//! A tag is made of whitespace separated words, each of which is one of:
+//! - an attribute: `bold`, `dim`, `italic`, `underline`, `blink`, `reverse`, `hidden` or `strike`,
+//! - a foreground color: a name such as `red` or `dark_grey`, or a hex code such as `#ff8800`,
+//! - a background color: `on` followed by a color,
+//! - a hyperlink: `link=` followed by the target, e.g. `[link=https://example.com]`.
+//!
+//! Brackets that do not form a valid tag are kept as they are,
+//! and `[[` can be used to write a literal `[` (see [`escape`]).
+//!
+//! Text is only interpreted as markup where it is enabled,
+//! e.g. with [`Format::Markup`](crate::text::Format::Markup) or `markup` on presets,
+//! so that plain text such as `[y/N]` is shown as it is by default.
+
+use std::sync::Arc;
+
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    style::{parse_attribute, parse_color},
+};
+
+/// Parses `markup` into `StyledGraphemes`,
+/// using `base` as the style of text outside of any tag.
+pub fn parse<S: AsRef<str>>(markup: S, base: ContentStyle) -> StyledGraphemes {
+    let markup = markup.as_ref();
+    let mut ret = StyledGraphemes::default();
+    let mut stack: Vec<(ContentStyle, Option<Arc<str>>)> = vec![(base, None)];
+    let mut rest = markup;
+
+    while let Some(ch) = rest.chars().next() {
+        let (style, link) = stack.last().cloned().unwrap_or((base, None));
+        let styled = |ch: char| {
+            let mut styled = StyledGrapheme::new(ch, style);
+            styled.apply_link(link.clone());
+            styled
+        };
+        if let Some(after) = rest.strip_prefix("[[") {
+            ret.push_joined(styled('['));
+            rest = after;
+            continue;
+        }
+        if ch == '[' {
+            if let Some(end) = rest.find(']') {
+                let tag = &rest[1..end];
+                if tag.starts_with('/') {
+                    if stack.len() > 1 {
+                        stack.pop();
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                } else if let Some(next) = apply_tag((style, link.clone()), tag) {
+                    stack.push(next);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        ret.push_joined(styled(ch));
+        rest = &rest[ch.len_utf8()..];
+    }
+    ret
+}
+
+/// Escapes `[` in `text` so that it is rendered literally by [`parse`],
+/// e.g. for user input embedded in an error message.
+pub fn escape<S: AsRef<str>>(text: S) -> String {
+    text.as_ref().replace('[', "[[")
+}
+
+fn apply_tag(
+    (mut style, mut link): (ContentStyle, Option<Arc<str>>),
+    tag: &str,
+) -> Option<(ContentStyle, Option<Arc<str>>)> {
+    let mut words = tag.split_whitespace().peekable();
+    words.peek()?;
+    while let Some(word) = words.next() {
+        if let Some(url) = word.strip_prefix("link=") {
+            link = Some(Arc::from(url));
+        } else if word == "on" {
+            style.background_color = Some(parse_color(words.next()?)?);
+        } else if let Some(attr) = parse_attribute(word) {
+            style.attributes.set(attr);
+        } else {
+            style.foreground_color = Some(parse_color(word)?);
+        }
+    }
+    Some((style, link))
+}
+
+#[cfg(test)]
+mod test {
+    mod parse {
+        use crate::{
+            crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+            grapheme::StyledGraphemes,
+            markup::parse,
+            style::StyleBuilder,
+        };
+
+        #[test]
+        fn test() {
+            let bold = StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            let bold_red = StyleBuilder::new()
+                .fgc(Color::Red)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_iter([
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from_str("b", bold),
+                    StyledGraphemes::from_str("c", bold_red),
+                    StyledGraphemes::from_str("d", bold),
+                    StyledGraphemes::from("e"),
+                ]),
+                parse("a[bold]b[red]c[/]d[/]e", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_with_background_and_hex() {
+            let style = StyleBuilder::new()
+                .fgc(Color::Rgb {
+                    r: 0xff,
+                    g: 0x88,
+                    b: 0x00,
+                })
+                .bgc(Color::Black)
+                .build();
+            assert_eq!(
+                StyledGraphemes::from_str("x", style),
+                parse("[#ff8800 on black]x[/]", ContentStyle::default()),
+            );
+        }
+
+        #[test]
+        fn test_with_link() {
+            let styled = parse(
+                "see [link=https://example.com]docs[/]",
+                ContentStyle::default(),
+            );
+            assert_eq!("see docs", styled.to_string());
+            assert_eq!(None, styled[0].link());
+            assert_eq!(Some("https://example.com"), styled[4].link());
+        }
+
+        #[test]
+        fn test_with_invalid_tags() {
+            assert_eq!(
+                "[y/n] [unknown]x[/] ]",
+                parse("[y/n] [unknown]x[/] ]", ContentStyle::default()).to_string(),
+            );
+        }
+
+        #[test]
+        fn test_with_escape() {
+            assert_eq!(
+                "[bold]",
+                parse(crate::markup::escape("[bold]"), ContentStyle::default()).to_string(),
+            );
+        }
+    }
+}
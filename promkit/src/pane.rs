@@ -27,6 +27,40 @@ impl Pane {
         self.layout.is_empty()
     }
 
+    /// Returns the display width of the widest row.
+    pub fn widths(&self) -> usize {
+        self.layout
+            .iter()
+            .map(|row| row.widths())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Appends the rows of `other` below the rows of this pane,
+    /// keeping the offset of this pane.
+    pub fn join(mut self, other: &Pane) -> Self {
+        self.layout.extend(other.layout.iter().cloned());
+        self
+    }
+
+    /// Draws `popup` on top of this pane with its top-left corner at (`row`, `column`).
+    /// Rows of `popup` are padded to the same width so that it covers a rectangle,
+    /// and rows are appended if `popup` extends past the bottom of this pane.
+    pub fn overlay(mut self, popup: &Pane, row: usize, column: usize) -> Self {
+        let popup_width = popup.widths();
+        for (i, popup_row) in popup.extract(popup.visible_row_count()).iter().enumerate() {
+            while self.layout.len() <= row + i {
+                self.layout.push(StyledGraphemes::default());
+            }
+            let popup_row = StyledGraphemes::from_iter([
+                popup_row,
+                &StyledGraphemes::from(" ".repeat(popup_width - popup_row.widths())),
+            ]);
+            self.layout[row + i] = self.layout[row + i].overlay(&popup_row, column);
+        }
+        self
+    }
+
     pub fn extract(&self, viewport_height: usize) -> Vec<StyledGraphemes> {
         let lines = self.layout.len().min(viewport_height);
         let mut start = self.offset;
@@ -54,6 +88,8 @@ mod test {
             let state = text::State {
                 text: "".to_string(),
                 style: ContentStyle::default(),
+                wrap: Default::default(),
+                format: Default::default(),
             };
             assert_eq!(0, state.create_pane(10, 10).visible_row_count())
         }
@@ -64,16 +100,37 @@ mod test {
 
         #[test]
         fn test() {
+            assert!(Pane {
+                layout: StyledGraphemes::from("").matrixify(10, 10, 0).0,
+                offset: 0,
+            }
+            .is_empty());
+        }
+    }
+    mod overlay {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            let pane = Pane::new(
+                vec![StyledGraphemes::from("aaaa"), StyledGraphemes::from("bbbb")],
+                0,
+            );
+            let popup = Pane::new(
+                vec![StyledGraphemes::from("XX"), StyledGraphemes::from("Y")],
+                0,
+            );
             assert_eq!(
-                true,
-                Pane {
-                    layout: StyledGraphemes::from("").matrixify(10, 10, 0).0,
-                    offset: 0,
-                }
-                .is_empty()
+                vec![
+                    StyledGraphemes::from("aaaa"),
+                    StyledGraphemes::from("bXXb"),
+                    StyledGraphemes::from(" Y "),
+                ],
+                pane.overlay(&popup, 1, 1).extract(10)
             );
         }
     }
+
     mod extract {
         use super::super::*;
 
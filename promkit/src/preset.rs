@@ -1,3 +1,5 @@
+mod footer;
+
 /// Provides a checkbox interface for multiple options selection.
 pub mod checkbox;
 
@@ -18,3 +20,6 @@ pub mod query_selector;
 pub mod tree;
 
 pub mod form;
+
+/// Provides a multi-line text editor.
+pub mod text_area;
@@ -0,0 +1,52 @@
+use std::{cell::RefCell, time::Duration};
+
+use crate::{
+    binding::Bindings, crossterm::event::Event, help, keyhint, snapshot::Snapshot,
+    switch::ActiveKeySwitcher,
+};
+
+/// Lets the help overlay, if enabled, handle `event` with the hints of the active keymap.
+/// Returns `true` if the overlay consumed the event.
+pub(crate) fn evaluate_help<S>(
+    help_snapshot: &mut Option<Snapshot<help::State>>,
+    keymap: &RefCell<ActiveKeySwitcher<S>>,
+    event: &Event,
+) -> bool {
+    help_snapshot.as_mut().is_some_and(|help_snapshot| {
+        help_snapshot.after_mut().hints = keymap.borrow().hints().to_vec();
+        help_snapshot.after_mut().evaluate(event)
+    })
+}
+
+/// Shows the hints of the active keymap in the footer, if enabled,
+/// along with the keys of a chord pressed so far.
+pub(crate) fn refresh<S>(
+    footer_snapshot: &mut Option<Snapshot<keyhint::State>>,
+    keymap: &RefCell<ActiveKeySwitcher<S>>,
+    bindings: &Bindings,
+) {
+    if let Some(footer_snapshot) = footer_snapshot {
+        footer_snapshot.after_mut().hints = keymap.borrow().hints().to_vec();
+    }
+    refresh_pending(footer_snapshot, bindings);
+}
+
+/// Shows the keys of a chord pressed so far in the footer, if enabled,
+/// hiding them once the chord times out.
+pub(crate) fn refresh_pending(
+    footer_snapshot: &mut Option<Snapshot<keyhint::State>>,
+    bindings: &Bindings,
+) {
+    if let Some(footer_snapshot) = footer_snapshot {
+        footer_snapshot.after_mut().pending = bindings.pending().map(|chord| chord.to_string());
+    }
+}
+
+/// How long to wait for the chord pressed so far to time out,
+/// if it is shown in the footer (see [`crate::Renderer::tick_interval`]).
+pub(crate) fn tick_interval(
+    footer_snapshot: &Option<Snapshot<keyhint::State>>,
+    bindings: &Bindings,
+) -> Option<Duration> {
+    footer_snapshot.as_ref().and(bindings.pending_timeout())
+}
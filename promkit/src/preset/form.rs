@@ -1,11 +1,22 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
+    binding::{Action, BindingOverrides, Bindings, Chord},
     core::Cursor,
-    crossterm::style::{Attribute, Attributes},
+    crossterm::{
+        event::{KeyCode, KeyEvent, KeyModifiers},
+        style::{Attribute, Attributes},
+    },
+    help, keyhint,
+    snapshot::Snapshot,
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text_editor, Prompt,
+    text_editor::{
+        self,
+        vi::{Vi, ViMode},
+    },
+    theme::default_theme,
+    Prompt,
 };
 
 mod keymap;
@@ -14,9 +25,17 @@ mod render;
 /// `Form` struct provides functionality for managing multiple text input fields.
 pub struct Form {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
     text_editor_states: Vec<text_editor::State>,
+    /// State of vi editing, if enabled with [`Self::vi_mode`].
+    vi: Option<Vi>,
     /// Overwrite the default styles of text editor states when unselected.
     overwrite_styles: Vec<render::Style>,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl Form {
@@ -39,13 +58,94 @@ impl Form {
             })
             .unzip();
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap),
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
             text_editor_states,
+            vi: Default::default(),
             overwrite_styles,
+            footer_state: Default::default(),
+            help_state: Default::default(),
         }
     }
 
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    /// Enables modal editing in the style of vi (see [`text_editor::vi`]) for all fields.
+    /// The form starts in the insert mode, and the current mode is shown before the prefix
+    /// of the focused field.
+    pub fn vi_mode(mut self) -> Self {
+        self.vi = Some(Vi::default());
+        self.keymap = self
+            .keymap
+            .register(
+                "vi_insert",
+                Rc::new(self::keymap::vi_insert) as keymap::Keymap,
+            )
+            .register("vi_normal", Rc::new(self::keymap::vi_normal))
+            .register("vi_visual", Rc::new(self::keymap::vi_visual))
+            .register_hints("vi_visual", self::keymap::vi_visual_hints());
+        self.keymap.switch(ViMode::Insert.keymap_name());
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>F1</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
+        self
+    }
+
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        if let (Some(vi), Some(state)) = (&self.vi, self.text_editor_states.first_mut()) {
+            vi.show_indicator(state);
+        }
         let default_styles = self
             .text_editor_states
             .iter()
@@ -55,11 +155,36 @@ impl Form {
                 inactive_char_style: state.inactive_char_style,
             })
             .collect();
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        if self.vi.is_some() {
+            self.keymap = self
+                .keymap
+                .register_hints_if_absent(
+                    "vi_insert",
+                    self::keymap::vi_insert_hints(&self.bindings),
+                )
+                .register_hints_if_absent(
+                    "vi_normal",
+                    self::keymap::vi_normal_hints(&self.bindings),
+                );
+        }
+        let keymap_hints = self.keymap.hints().to_vec();
         let mut renderer = render::Renderer {
             keymap: RefCell::new(self.keymap),
+            bindings: self.bindings,
             text_editor_states: Cursor::new(self.text_editor_states, 0, false),
+            vi: self.vi,
+            kill_ring: Default::default(),
+            clipboard: Default::default(),
             default_styles,
             overwrite_styles: self.overwrite_styles,
+            footer_snapshot: self.footer_state.map(|mut state| {
+                state.hints = keymap_hints;
+                Snapshot::<keyhint::State>::new(state)
+            }),
+            help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
         };
         renderer.overwrite_styles();
         Ok(Prompt { renderer })
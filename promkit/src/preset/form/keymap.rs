@@ -1,188 +1,206 @@
+use std::rc::Rc;
+
 use crate::{
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
-    preset, text_editor, PromptSignal,
+    binding::{self, Action, Bindings, KeyPress},
+    crossterm::event::{Event, KeyCode},
+    keyhint::KeyHint,
+    locale, preset,
+    text_editor::vi::{self, Outcome, ViMode},
+    PromptSignal,
 };
 
-pub type Keymap = fn(
-    event: &Event,
-    renderer: &mut preset::form::render::Renderer,
-) -> anyhow::Result<PromptSignal>;
+/// A handler of events, which can be a closure capturing the context of the application.
+/// It is reference-counted so that the renderer can call it while being borrowed mutably.
+pub type Keymap =
+    Rc<dyn Fn(&Event, &mut preset::form::render::Renderer) -> anyhow::Result<PromptSignal>>;
 
+/// Default key bindings for the form, extending [`binding::text_editing`].
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>       | Exit the form
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>←</kbd>           | Move the cursor one character to the left
+/// | <kbd>→</kbd>           | Move the cursor one character to the right
+/// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the field
+/// | <kbd>Ctrl + E</kbd>    | Move the cursor to the end of the field
+/// | <kbd>Alt + B</kbd>     | Move the cursor to the previous nearest character within set (default: whitespace)
+/// | <kbd>Alt + F</kbd>     | Move the cursor to the next nearest character within set (default: whitespace)
+/// | <kbd>Backspace</kbd>   | Delete the character before the cursor
+/// | <kbd>Ctrl + U</kbd>    | Delete all characters of the field
+/// | <kbd>Ctrl + Y</kbd>    | Insert the most recently erased text
+/// | <kbd>Alt + Y</kbd>     | Replace the text just inserted with the text erased before it
+/// | <kbd>Ctrl + Z</kbd>    | Undo the last edit, also with <kbd>Ctrl + _</kbd>
+/// | <kbd>Alt + Z</kbd>     | Redo the edit undone last
+/// | <kbd>Shift + ←/→</kbd> | Select the character to the left or right
+/// | <kbd>Shift + Home/End</kbd>| Select to the start or end of the input
+/// | <kbd>Alt + C</kbd>     | Copy the selection to the clipboard
+/// | <kbd>Alt + X</kbd>     | Cut the selection to the clipboard
+/// | <kbd>Alt + V</kbd>     | Paste from the clipboard
+/// | <kbd>Ctrl + W</kbd>    | Erase to the previous nearest character within set (default: whitespace)
+/// | <kbd>Alt + D</kbd>     | Erase to the next nearest character within set (default: whitespace)
+/// | <kbd>↑</kbd>           | Move to the previous field
+/// | <kbd>↓</kbd>           | Move to the next field
+pub fn default_bindings() -> Bindings {
+    let mut bindings = Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Enter), Action::Submit),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+        (KeyPress::plain(KeyCode::Up), Action::MoveUp),
+        (KeyPress::plain(KeyCode::Down), Action::MoveDown),
+    ]);
+    bindings.extend(binding::text_editing());
+    bindings
+}
+
+/// Performs the action bound to the key in [`Renderer::bindings`](preset::form::render::Renderer::bindings)
+/// (see [`default_bindings`]), and inserts characters typed without a binding into the current field.
 pub fn default(
     event: &Event,
     renderer: &mut preset::form::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
     let current_position = renderer.text_editor_states.position();
+    let action = renderer.bindings.lookup(event);
+    if !renderer.bindings.is_pending()
+        && !action.is_some_and(|action| {
+            action.kills() || matches!(action, Action::Yank | Action::YankPop)
+        })
+    {
+        renderer.kill_ring.interrupt();
+    }
+    let text_editor_mut = &mut renderer.text_editor_states.contents_mut()[current_position];
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
-
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Left,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .backward();
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Right,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .forward();
+    match action {
+        Some(Action::Submit) => return Ok(PromptSignal::Quit),
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
+
+        Some(Action::MoveUp) => {
+            renderer.text_editor_states.backward();
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('a'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .move_to_head(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('e'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .move_to_tail(),
-
-        // Move cursor to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('b'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .move_to_previous_nearest(&word_break_chars)
+        Some(Action::MoveDown) => {
+            renderer.text_editor_states.forward();
         }
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('f'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .move_to_next_nearest(&word_break_chars)
+        Some(action) => {
+            if !text_editor_mut.perform_with_clipboard(action, &mut renderer.clipboard) {
+                text_editor_mut.perform_with_kill_ring(action, &mut renderer.kill_ring);
+            }
         }
 
-        // Erase char(s).
-        Event::Key(KeyEvent {
-            code: KeyCode::Backspace,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .erase(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('u'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .erase_all(),
-
-        // Erase to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('w'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .erase_to_previous_nearest(&word_break_chars)
+        // Keys consumed as the first keys of a chord are not typed.
+        None if renderer.bindings.is_pending() => (),
+        None => {
+            if let Some(ch) = binding::input_char(event) {
+                text_editor_mut.input(ch);
+            }
         }
+    }
+    Ok(PromptSignal::Continue)
+}
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('d'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .erase_to_next_nearest(&word_break_chars)
-        }
+/// Key hints for [`default`], built from the keys bound in `bindings`.
+pub fn default_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    bindings.hints(&locale::messages(), |_| true)
+}
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.backward();
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.forward();
+/// Keymap of the insert mode of vi editing (see [`Form::vi_mode`](preset::form::Form::vi_mode)):
+/// <kbd>Esc</kbd> enters the normal mode and other keys work as in [`default`].
+pub fn vi_insert(
+    event: &Event,
+    renderer: &mut preset::form::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let current_position = renderer.text_editor_states.position();
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return default(event, renderer);
+    };
+    if renderer.bindings.is_pending() {
+        return default_with_focus(event, renderer);
+    }
+    let text_editor_mut = &mut renderer.text_editor_states.contents_mut()[current_position];
+    match vi.insert(key, text_editor_mut) {
+        Outcome::Unhandled => default_with_focus(event, renderer),
+        outcome => {
+            renderer.kill_ring.interrupt();
+            switch_vi_mode(outcome, renderer);
+            Ok(PromptSignal::Continue)
         }
+    }
+}
 
-        // Input char.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::SHIFT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => match renderer.text_editor_states.contents_mut()[current_position].edit_mode {
-            text_editor::Mode::Insert => renderer.text_editor_states.contents_mut()
-                [current_position]
-                .texteditor
-                .insert(*ch),
-            text_editor::Mode::Overwrite => renderer.text_editor_states.contents_mut()
-                [current_position]
-                .texteditor
-                .overwrite(*ch),
-        },
-
-        _ => (),
+/// Keymap of the normal mode of vi editing (see [`vi`](crate::text_editor::vi)).
+/// Keys bound to actions other than editing text (e.g. <kbd>↑</kbd>) work as in [`default`].
+pub fn vi_normal(
+    event: &Event,
+    renderer: &mut preset::form::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let current_position = renderer.text_editor_states.position();
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return Ok(PromptSignal::Continue);
+    };
+    if renderer.bindings.is_pending()
+        || renderer
+            .bindings
+            .get(key)
+            .is_some_and(|action| !action.edits_text())
+    {
+        return default_with_focus(event, renderer);
     }
+    let text_editor_mut = &mut renderer.text_editor_states.contents_mut()[current_position];
+    let outcome = match vi.mode() {
+        ViMode::Visual => vi.visual(key, text_editor_mut),
+        _ => vi.normal(key, text_editor_mut),
+    };
+    renderer.kill_ring.interrupt();
+    switch_vi_mode(outcome, renderer);
     Ok(PromptSignal::Continue)
 }
+
+/// Keymap of the visual mode of vi editing, which shares [`vi_normal`].
+pub fn vi_visual(
+    event: &Event,
+    renderer: &mut preset::form::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    vi_normal(event, renderer)
+}
+
+/// Performs [`default`], moving the indicator of the vi mode to the field focused afterwards.
+fn default_with_focus(
+    event: &Event,
+    renderer: &mut preset::form::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let previous_position = renderer.text_editor_states.position();
+    let signal = default(event, renderer);
+    let current_position = renderer.text_editor_states.position();
+    if let Some(vi) = &renderer.vi {
+        if current_position != previous_position {
+            let states = renderer.text_editor_states.contents_mut();
+            vi.hide_indicator(&mut states[previous_position]);
+            vi.show_indicator(&mut states[current_position]);
+        }
+    }
+    signal
+}
+
+/// Activates the keymap of the vi mode entered by a key.
+fn switch_vi_mode(outcome: Outcome, renderer: &mut preset::form::render::Renderer) {
+    if let Outcome::Switched(mode) = outcome {
+        renderer.keymap.borrow_mut().switch(mode.keymap_name());
+    }
+}
+
+/// Key hints for [`vi_insert`], built from the keys bound in `bindings`.
+pub fn vi_insert_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    vi::insert_hints(default_hints(bindings))
+}
+
+/// Key hints for [`vi_normal`], followed by the keys bound in `bindings`
+/// to actions other than editing text.
+pub fn vi_normal_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    let mut hints = vi::normal_hints();
+    hints.extend(bindings.hints(&locale::messages(), |action| !action.edits_text()));
+    hints
+}
+
+/// Key hints for [`vi_visual`].
+pub fn vi_visual_hints() -> Vec<KeyHint> {
+    vi::visual_hints()
+}
@@ -1,11 +1,16 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
+    binding::Bindings,
     core::Cursor,
     crossterm::{event::Event, style::ContentStyle},
+    help, keyhint,
     pane::Pane,
+    preset::footer,
+    snapshot::Snapshot,
     switch::ActiveKeySwitcher,
-    text_editor, PaneFactory, PromptSignal,
+    text_editor::{self, vi::Vi, Clipboard, KillRing},
+    PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -24,12 +29,24 @@ pub struct Style {
 pub struct Renderer {
     /// A mutable reference to a key switcher that manages active key mappings.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// Keys and the actions they perform in the default keymap.
+    pub bindings: Bindings,
     /// Cursor managing the state of multiple text editors.
     pub text_editor_states: Cursor<Vec<text_editor::State>>,
+    /// State of vi editing shared by all fields, if enabled.
+    pub vi: Option<Vi>,
+    /// Text erased by the keymap, which can be inserted again.
+    pub kill_ring: KillRing,
+    /// Text copied by the keymap, which can be pasted.
+    pub clipboard: Clipboard,
     /// Default styles applied to text editors.
     pub default_styles: Vec<Style>,
     /// Styles applied to text editors when they are unselected.
     pub overwrite_styles: Vec<Style>,
+    /// Optional snapshot of the footer renderer.
+    pub footer_snapshot: Option<Snapshot<keyhint::State>>,
+    /// Optional snapshot of the help overlay renderer.
+    pub help_snapshot: Option<Snapshot<help::State>>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -69,17 +86,43 @@ impl Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        self.text_editor_states
+        if let Some(help_snapshot) = &self.help_snapshot {
+            if help_snapshot.after().visible {
+                return vec![help_snapshot.create_pane(width, height)];
+            }
+        }
+        let mut panes: Vec<Pane> = self
+            .text_editor_states
             .contents()
             .iter()
             .map(|state| state.create_pane(width, height))
-            .collect()
+            .collect();
+        if let Some(footer_snapshot) = &self.footer_snapshot {
+            panes.push(footer_snapshot.create_pane(width, height));
+        }
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
-        let keymap = *self.keymap.borrow_mut().get();
+        if footer::evaluate_help(&mut self.help_snapshot, &self.keymap, event) {
+            return Ok(PromptSignal::Continue);
+        }
+        let keymap = Rc::clone(self.keymap.borrow().get());
         let signal = keymap(event, self);
         self.overwrite_styles();
+        footer::refresh(&mut self.footer_snapshot, &self.keymap, &self.bindings);
         signal
     }
+
+    fn take_copied_text(&mut self) -> Option<String> {
+        self.clipboard.take_unsent()
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        footer::tick_interval(&self.footer_snapshot, &self.bindings)
+    }
+
+    fn tick(&mut self) {
+        footer::refresh_pending(&mut self.footer_snapshot, &self.bindings);
+    }
 }
@@ -1,12 +1,20 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::{Attribute, ContentStyle},
+    },
+    grapheme::Wrap,
+    help,
     json::{self, JsonStream},
+    keyhint::{self, KeyHint},
     snapshot::Snapshot,
-    style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text, Prompt,
+    text,
+    theme::default_theme,
+    Prompt, PromptSignal,
 };
 
 pub mod keymap;
@@ -15,38 +23,48 @@ pub mod render;
 /// Represents a JSON preset for rendering JSON data and titles with customizable styles.
 pub struct Json {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
     title_state: text::State,
     json_state: json::State,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl Json {
     pub fn new(stream: JsonStream) -> Self {
+        let theme = default_theme();
         Self {
             title_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
             json_state: json::State {
                 stream,
-                curly_brackets_style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
-                square_brackets_style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
-                key_style: StyleBuilder::new().fgc(Color::DarkBlue).build(),
-                string_value_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
-                number_value_style: StyleBuilder::new().build(),
-                boolean_value_style: StyleBuilder::new().build(),
-                null_value_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+                curly_brackets_style: theme.json_curly_brackets_style,
+                square_brackets_style: theme.json_square_brackets_style,
+                key_style: theme.json_key_style,
+                string_value_style: theme.json_string_value_style,
+                number_value_style: theme.json_number_value_style,
+                boolean_value_style: theme.json_boolean_value_style,
+                null_value_style: theme.json_null_value_style,
                 active_item_attribute: Attribute::Undercurled,
                 inactive_item_attribute: Attribute::Dim,
                 lines: Default::default(),
                 indent: 2,
+                wrap: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
+            footer_state: Default::default(),
+            help_state: Default::default(),
         }
     }
 
@@ -62,12 +80,25 @@ impl Json {
         self
     }
 
+    /// Interprets markup tags (see [`crate::markup`]) in the title,
+    /// e.g. `"[bold]name[/]"`, instead of showing it as it is.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self
+    }
+
     /// Sets the number of lines to be used for rendering the JSON data.
     pub fn json_lines(mut self, lines: usize) -> Self {
         self.json_state.lines = Some(lines);
         self
     }
 
+    /// Sets how lines wider than the terminal are laid out.
+    pub fn json_wrap(mut self, wrap: Wrap) -> Self {
+        self.json_state.wrap = wrap;
+        self
+    }
+
     /// Sets the indentation level for rendering the JSON data.
     pub fn indent(mut self, indent: usize) -> Self {
         self.json_state.indent = indent;
@@ -86,18 +117,96 @@ impl Json {
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
-        self.keymap = self.keymap.register(key, handler);
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>?</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
         self
     }
 
     /// Creates a prompt based on the current configuration of the `Json` instance.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        let keymap_hints = self.keymap.hints().to_vec();
         Ok(Prompt {
             renderer: render::Renderer {
                 keymap: RefCell::new(self.keymap),
+                bindings: self.bindings,
                 title_snapshot: Snapshot::<text::State>::new(self.title_state),
                 json_snapshot: Snapshot::<json::State>::new(self.json_state),
+                footer_snapshot: self.footer_state.map(|mut state| {
+                    state.hints = keymap_hints;
+                    Snapshot::<keyhint::State>::new(state)
+                }),
+                help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
             },
         })
     }
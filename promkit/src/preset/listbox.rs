@@ -1,12 +1,21 @@
-use std::{cell::RefCell, fmt::Display};
+use std::{cell::RefCell, fmt::Display, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
-    listbox,
+    ansi,
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::ContentStyle,
+    },
+    grapheme::Wrap,
+    help,
+    keyhint::{self, KeyHint},
+    listbox, locale, markup,
     snapshot::Snapshot,
-    style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text, Prompt,
+    text,
+    theme::default_theme,
+    Prompt, PromptSignal,
 };
 
 pub mod keymap;
@@ -15,10 +24,16 @@ pub mod render;
 /// A component for creating and managing a selectable list of options.
 pub struct Listbox {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
     /// State for the title displayed above the selectable list.
     title_state: text::State,
     /// State for the selectable list itself.
     listbox_state: listbox::State,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl Listbox {
@@ -28,26 +43,51 @@ impl Listbox {
     /// # Arguments
     ///
     /// * `items` - An iterator over items
-    /// that implement the `Display` trait, to be used as options.
+    ///   that implement the `Display` trait, to be used as options.
     pub fn new<T: Display, I: IntoIterator<Item = T>>(items: I) -> Self {
+        let theme = default_theme();
+        let messages = locale::messages();
         Self {
             title_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
             listbox_state: listbox::State {
                 listbox: listbox::Listbox::from_iter(items),
-                cursor: String::from("❯ "),
-                active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
-                inactive_item_style: Some(StyleBuilder::new().build()),
+                cursor: messages.cursor,
+                active_item_style: Some(theme.active_item_style),
+                inactive_item_style: Some(theme.inactive_item_style),
                 lines: Default::default(),
+                wrap: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
+            footer_state: Default::default(),
+            help_state: Default::default(),
         }
     }
 
+    /// Constructs a new `Listbox` instance
+    /// whose items are written in markup (see [`crate::markup`]),
+    /// e.g. `"[bold]main.rs[/] (modified)"`.
+    /// Unselected items are shown in their own styles unless [`Self::inactive_item_style`] is set.
+    /// The selected item is returned without the tags.
+    pub fn new_with_markup<T: AsRef<str>, I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut ret = Self::new(Vec::<String>::new());
+        ret.listbox_state.listbox = listbox::Listbox::from_iter(
+            items
+                .into_iter()
+                .map(|item| markup::parse(item, ContentStyle::default())),
+        );
+        ret.listbox_state.inactive_item_style = None;
+        ret
+    }
+
     /// Sets the title text displayed above the selectable list.
     pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
         self.title_state.text = text.as_ref().to_string();
@@ -60,6 +100,13 @@ impl Listbox {
         self
     }
 
+    /// Interprets markup tags (see [`crate::markup`]) in the title,
+    /// e.g. `"[bold]name[/]"`, instead of showing it as it is.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self
+    }
+
     /// Sets the cursor symbol used to indicate the current selection.
     pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.listbox_state.cursor = cursor.as_ref().to_string();
@@ -84,20 +131,116 @@ impl Listbox {
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
-        self.keymap = self.keymap.register(key, handler);
+    /// Sets how items wider than the terminal are laid out.
+    pub fn listbox_wrap(mut self, wrap: Wrap) -> Self {
+        self.listbox_state.wrap = wrap;
+        self
+    }
+
+    /// Interprets ANSI escape sequences in the items (e.g. the colored output of `git`)
+    /// as styles instead of rendering them as they are.
+    /// Unselected items are shown in their own styles unless [`Self::inactive_item_style`] is set afterwards.
+    /// The selected item is returned without the escape sequences.
+    pub fn ansi_items(mut self) -> Self {
+        for item in self.listbox_state.listbox.items_mut() {
+            *item = ansi::parse(item.to_string(), ContentStyle::default());
+        }
+        self.listbox_state.inactive_item_style = None;
+        self
+    }
+
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>?</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
         self
     }
 
     /// Displays the select prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        let keymap_hints = self.keymap.hints().to_vec();
         Ok(Prompt {
             renderer: render::Renderer {
                 keymap: RefCell::new(self.keymap),
+                bindings: self.bindings,
                 title_snapshot: Snapshot::<text::State>::new(self.title_state),
                 listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
+                footer_snapshot: self.footer_state.map(|mut state| {
+                    state.hints = keymap_hints;
+                    Snapshot::<keyhint::State>::new(state)
+                }),
+                help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
             },
         })
     }
@@ -1,15 +1,16 @@
+use std::rc::Rc;
+
 use crate::{
-    crossterm::event::{
-        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
-        MouseEventKind,
-    },
-    preset, PromptSignal,
+    binding::{Action, Bindings, KeyPress},
+    crossterm::event::{Event, KeyCode, KeyModifiers, MouseEvent, MouseEventKind},
+    keyhint::KeyHint,
+    locale, preset, PromptSignal,
 };
 
-pub type Keymap = fn(
-    event: &Event,
-    renderer: &mut preset::listbox::render::Renderer,
-) -> anyhow::Result<PromptSignal>;
+/// A handler of events, which can be a closure capturing the context of the application.
+/// It is reference-counted so that the renderer can call it while being borrowed mutably.
+pub type Keymap =
+    Rc<dyn Fn(&Event, &mut preset::listbox::render::Renderer) -> anyhow::Result<PromptSignal>>;
 
 /// Default key bindings for the listbox.
 ///
@@ -19,58 +20,60 @@ pub type Keymap = fn(
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
+pub fn default_bindings() -> Bindings {
+    Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Enter), Action::Submit),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+        (KeyPress::plain(KeyCode::Up), Action::MoveUp),
+        (KeyPress::plain(KeyCode::Down), Action::MoveDown),
+    ])
+}
+
+/// Performs the action bound to the key in [`Renderer::bindings`](preset::listbox::render::Renderer::bindings)
+/// (see [`default_bindings`]). Scrolling the mouse wheel moves the selection.
 pub fn default(
     event: &Event,
     renderer: &mut preset::listbox::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    let action = match event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            modifiers: KeyModifiers::NONE,
+            ..
+        }) => Some(Action::MoveUp),
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            modifiers: KeyModifiers::NONE,
+            ..
+        }) => Some(Action::MoveDown),
+        _ => renderer.bindings.lookup(event),
+    };
     let listbox_after_mut = renderer.listbox_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+    match action {
+        Some(Action::Submit) => return Ok(PromptSignal::Quit),
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
 
         // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Mouse(MouseEvent {
-            kind: MouseEventKind::ScrollUp,
-            column: _,
-            row: _,
-            modifiers: KeyModifiers::NONE,
-        }) => {
+        Some(Action::MoveUp) => {
             listbox_after_mut.listbox.backward();
         }
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Mouse(MouseEvent {
-            kind: MouseEventKind::ScrollDown,
-            column: _,
-            row: _,
-            modifiers: KeyModifiers::NONE,
-        }) => {
+        Some(Action::MoveDown) => {
             listbox_after_mut.listbox.forward();
         }
+        Some(Action::MoveToHead) => {
+            listbox_after_mut.listbox.move_to_head();
+        }
+        Some(Action::MoveToTail) => {
+            listbox_after_mut.listbox.move_to_tail();
+        }
 
         _ => (),
     }
     Ok(PromptSignal::Continue)
 }
+
+/// Key hints for [`default`], built from the keys bound in `bindings`.
+pub fn default_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    bindings.hints(&locale::messages(), |_| true)
+}
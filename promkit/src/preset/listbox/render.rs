@@ -1,16 +1,22 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, snapshot::Snapshot, switch::ActiveKeySwitcher,
-    text, PaneFactory, PromptSignal,
+    binding::Bindings, crossterm::event::Event, help, keyhint, listbox, pane::Pane, preset::footer,
+    snapshot::Snapshot, switch::ActiveKeySwitcher, text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
 
 pub struct Renderer {
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// Keys and the actions they perform in the default keymap.
+    pub bindings: Bindings,
     pub title_snapshot: Snapshot<text::State>,
     pub listbox_snapshot: Snapshot<listbox::State>,
+    /// Optional snapshot of the footer renderer.
+    pub footer_snapshot: Option<Snapshot<keyhint::State>>,
+    /// Optional snapshot of the help overlay renderer.
+    pub help_snapshot: Option<Snapshot<help::State>>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -23,14 +29,36 @@ impl crate::Finalizer for Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
+        if let Some(help_snapshot) = &self.help_snapshot {
+            if help_snapshot.after().visible {
+                return vec![help_snapshot.create_pane(width, height)];
+            }
+        }
+        let mut panes = vec![
             self.title_snapshot.create_pane(width, height),
             self.listbox_snapshot.create_pane(width, height),
-        ]
+        ];
+        if let Some(footer_snapshot) = &self.footer_snapshot {
+            panes.push(footer_snapshot.create_pane(width, height));
+        }
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
-        let keymap = *self.keymap.borrow_mut().get();
-        keymap(event, self)
+        if footer::evaluate_help(&mut self.help_snapshot, &self.keymap, event) {
+            return Ok(PromptSignal::Continue);
+        }
+        let keymap = Rc::clone(self.keymap.borrow().get());
+        let signal = keymap(event, self);
+        footer::refresh(&mut self.footer_snapshot, &self.keymap, &self.bindings);
+        signal
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        footer::tick_interval(&self.footer_snapshot, &self.bindings)
+    }
+
+    fn tick(&mut self) {
+        footer::refresh_pending(&mut self.footer_snapshot, &self.bindings);
     }
 }
@@ -1,14 +1,26 @@
-use std::{cell::RefCell, fmt::Display, iter::FromIterator};
+use std::{cell::RefCell, fmt::Display, iter::FromIterator, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    ansi,
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::ContentStyle,
+    },
+    help,
+    keyhint::{self, KeyHint},
     listbox::{self, Listbox},
+    locale,
     snapshot::Snapshot,
-    style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text,
-    text_editor::{self, Mode},
-    Prompt,
+    text_editor::{
+        self,
+        vi::{Vi, ViMode},
+        Mode,
+    },
+    theme::default_theme,
+    Prompt, PromptSignal,
 };
 
 pub mod keymap;
@@ -19,15 +31,23 @@ pub mod render;
 /// for displaying filtered options based on the input.
 pub struct QuerySelector {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
     /// State for the title displayed above the query selection.
     title_state: text::State,
     /// State for the text editor component.
     text_editor_state: text_editor::State,
+    /// State of vi editing, if enabled with [`Self::vi_mode`].
+    vi: Option<Vi>,
     /// State for the list box component.
     listbox_state: listbox::State,
     /// A filter function to apply to the list box items
     /// based on the text editor input.
     filter: render::Filter,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl QuerySelector {
@@ -37,43 +57,50 @@ impl QuerySelector {
     /// # Arguments
     ///
     /// * `items` - An iterator over items that implement the `Display` trait,
-    /// to be used as options in the list box.
+    ///   to be used as options in the list box.
     /// * `filter` - A function that takes the current input
-    /// from the text editor and the list of items,
-    /// returning a filtered list of items to display.
-    pub fn new<T, I>(items: I, filter: render::Filter) -> Self
+    ///   from the text editor and the list of items,
+    ///   returning a filtered list of items to display.
+    pub fn new<T, I, F>(items: I, filter: F) -> Self
     where
         T: Display,
         I: IntoIterator<Item = T>,
+        F: Fn(&str, &Vec<String>) -> Vec<String> + 'static,
     {
+        let theme = default_theme();
+        let messages = locale::messages();
         Self {
             title_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
             text_editor_state: text_editor::State {
-                texteditor: Default::default(),
-                history: None,
-                prefix: String::from("❯❯ "),
-                mask: None,
-                prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
-                active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
-                inactive_char_style: StyleBuilder::new().build(),
-                edit_mode: Default::default(),
-                word_break_chars: Default::default(),
-                lines: Default::default(),
+                prefix: messages.prefix,
+                prefix_style: theme.prefix_style,
+                active_char_style: theme.active_char_style,
+                inactive_char_style: theme.inactive_char_style,
+                selection_style: theme.selection_style,
+                ..Default::default()
             },
             listbox_state: listbox::State {
                 listbox: Listbox::from_iter(items),
-                cursor: String::from("❯ "),
-                active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
-                inactive_item_style: Some(StyleBuilder::new().build()),
+                cursor: messages.cursor,
+                active_item_style: Some(theme.active_item_style),
+                inactive_item_style: Some(theme.inactive_item_style),
                 lines: Default::default(),
+                wrap: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
-            filter,
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
+            vi: Default::default(),
+            footer_state: Default::default(),
+            help_state: Default::default(),
+            filter: Box::new(filter),
         }
     }
 
@@ -89,6 +116,13 @@ impl QuerySelector {
         self
     }
 
+    /// Interprets markup tags (see [`crate::markup`]) in the title,
+    /// e.g. `"[bold]name[/]"`, instead of showing it as it is.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self
+    }
+
     /// Sets the prefix string displayed before the input text in the text editor component.
     pub fn prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
         self.text_editor_state.prefix = prefix.as_ref().to_string();
@@ -113,6 +147,12 @@ impl QuerySelector {
         self
     }
 
+    /// Sets the style for the characters selected in the input field.
+    pub fn selection_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.selection_style = style;
+        self
+    }
+
     /// Sets the editing mode for the text editor component.
     pub fn edit_mode(mut self, mode: Mode) -> Self {
         self.text_editor_state.edit_mode = mode;
@@ -149,22 +189,147 @@ impl QuerySelector {
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
-        self.keymap = self.keymap.register(key, handler);
+    /// Interprets ANSI escape sequences in the items (e.g. the colored output of `git`)
+    /// as styles instead of rendering them as they are.
+    /// Unselected items are shown in their own styles unless [`Self::inactive_item_style`] is set afterwards.
+    /// The selected item is returned without the escape sequences.
+    pub fn ansi_items(mut self) -> Self {
+        for item in self.listbox_state.listbox.items_mut() {
+            *item = ansi::parse(item.to_string(), ContentStyle::default());
+        }
+        self.listbox_state.inactive_item_style = None;
+        self
+    }
+
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables modal editing in the style of vi (see [`text_editor::vi`]).
+    /// The prompt starts in the insert mode, and the current mode is shown before the prefix.
+    pub fn vi_mode(mut self) -> Self {
+        self.vi = Some(Vi::default());
+        self.keymap = self
+            .keymap
+            .register(
+                "vi_insert",
+                Rc::new(self::keymap::vi_insert) as keymap::Keymap,
+            )
+            .register("vi_normal", Rc::new(self::keymap::vi_normal))
+            .register("vi_visual", Rc::new(self::keymap::vi_visual))
+            .register_hints("vi_visual", self::keymap::vi_visual_hints());
+        self.keymap.switch(ViMode::Insert.keymap_name());
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>F1</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
         self
     }
 
     /// Displays the query select prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        if let Some(vi) = &self.vi {
+            vi.show_indicator(&mut self.text_editor_state);
+        }
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        if self.vi.is_some() {
+            self.keymap = self
+                .keymap
+                .register_hints_if_absent(
+                    "vi_insert",
+                    self::keymap::vi_insert_hints(&self.bindings),
+                )
+                .register_hints_if_absent(
+                    "vi_normal",
+                    self::keymap::vi_normal_hints(&self.bindings),
+                );
+        }
+        let keymap_hints = self.keymap.hints().to_vec();
         Ok(Prompt {
             renderer: render::Renderer {
                 keymap: RefCell::new(self.keymap),
+                bindings: self.bindings,
                 title_snapshot: Snapshot::<text::State>::new(self.title_state),
                 text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+                vi: self.vi,
+                kill_ring: Default::default(),
+                clipboard: Default::default(),
                 listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
                 filter: self.filter,
+                footer_snapshot: self.footer_state.map(|mut state| {
+                    state.hints = keymap_hints;
+                    Snapshot::<keyhint::State>::new(state)
+                }),
+                help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
             },
         })
     }
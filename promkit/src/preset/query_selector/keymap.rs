@@ -1,115 +1,191 @@
+use std::rc::Rc;
+
 use crate::{
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
-    preset, text_editor, PromptSignal,
+    binding::{self, Action, Bindings, KeyPress},
+    crossterm::event::{Event, KeyCode},
+    keyhint::KeyHint,
+    locale, preset,
+    text_editor::vi::{self, Outcome, ViMode},
+    PromptSignal,
 };
 
-pub type Keymap = fn(
-    event: &Event,
-    renderer: &mut preset::query_selector::render::Renderer,
-) -> anyhow::Result<PromptSignal>;
+/// A handler of events, which can be a closure capturing the context of the application.
+/// It is reference-counted so that the renderer can call it while being borrowed mutably.
+pub type Keymap = Rc<
+    dyn Fn(&Event, &mut preset::query_selector::render::Renderer) -> anyhow::Result<PromptSignal>,
+>;
 
+/// Default key bindings for the query selector, extending [`binding::text_editing`]
+/// without the actions on words, as the query has no characters breaking words.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>       | Exit the query selector
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>←</kbd>           | Move the cursor one character to the left
+/// | <kbd>→</kbd>           | Move the cursor one character to the right
+/// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the query
+/// | <kbd>Ctrl + E</kbd>    | Move the cursor to the end of the query
+/// | <kbd>Backspace</kbd>   | Delete the character before the cursor
+/// | <kbd>Ctrl + U</kbd>    | Delete all characters of the query
+/// | <kbd>Ctrl + Y</kbd>    | Insert the most recently erased text
+/// | <kbd>Alt + Y</kbd>     | Replace the text just inserted with the text erased before it
+/// | <kbd>Ctrl + Z</kbd>    | Undo the last edit, also with <kbd>Ctrl + _</kbd>
+/// | <kbd>Alt + Z</kbd>     | Redo the edit undone last
+/// | <kbd>Shift + ←/→</kbd> | Select the character to the left or right
+/// | <kbd>Shift + Home/End</kbd>| Select to the start or end of the input
+/// | <kbd>Alt + C</kbd>     | Copy the selection to the clipboard
+/// | <kbd>Alt + X</kbd>     | Cut the selection to the clipboard
+/// | <kbd>Alt + V</kbd>     | Paste from the clipboard
+/// | <kbd>↑</kbd>           | Move the selection up
+/// | <kbd>↓</kbd>           | Move the selection down
+pub fn default_bindings() -> Bindings {
+    let mut bindings = Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Enter), Action::Submit),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+        (KeyPress::plain(KeyCode::Up), Action::MoveUp),
+        (KeyPress::plain(KeyCode::Down), Action::MoveDown),
+    ]);
+    bindings.extend(binding::text_editing().into_iter().filter(|(_, action)| {
+        !matches!(
+            action,
+            Action::MoveWordBackward
+                | Action::MoveWordForward
+                | Action::EraseWordBackward
+                | Action::EraseWordForward
+        )
+    }));
+    bindings
+}
+
+/// Performs the action bound to the key in [`Renderer::bindings`](preset::query_selector::render::Renderer::bindings)
+/// (see [`default_bindings`]), and inserts characters typed without a binding into the query.
 pub fn default(
     event: &Event,
     renderer: &mut preset::query_selector::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    let action = renderer.bindings.lookup(event);
+    if !renderer.bindings.is_pending()
+        && !action.is_some_and(|action| {
+            action.kills() || matches!(action, Action::Yank | Action::YankPop)
+        })
+    {
+        renderer.kill_ring.interrupt();
+    }
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
     let listbox_after_mut = renderer.listbox_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+    match action {
+        Some(Action::Submit) => return Ok(PromptSignal::Quit),
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
 
         // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Left,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.backward();
+        Some(Action::MoveUp) => {
+            listbox_after_mut.listbox.backward();
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Right,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.forward();
+        Some(Action::MoveDown) => {
+            listbox_after_mut.listbox.forward();
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('a'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_head(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('e'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_tail(),
-
-        // Erase char(s).
-        Event::Key(KeyEvent {
-            code: KeyCode::Backspace,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('u'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase_all(),
 
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            listbox_after_mut.listbox.backward();
+        Some(action) => {
+            if !text_editor_after_mut.perform_with_clipboard(action, &mut renderer.clipboard) {
+                text_editor_after_mut.perform_with_kill_ring(action, &mut renderer.kill_ring);
+            }
         }
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            listbox_after_mut.listbox.forward();
+        // Keys consumed as the first keys of a chord are not typed.
+        None if renderer.bindings.is_pending() => (),
+        None => {
+            if let Some(ch) = binding::input_char(event) {
+                text_editor_after_mut.input(ch);
+            }
         }
+    }
+    Ok(PromptSignal::Continue)
+}
 
-        // Input char.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::SHIFT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => match text_editor_after_mut.edit_mode {
-            text_editor::Mode::Insert => text_editor_after_mut.texteditor.insert(*ch),
-            text_editor::Mode::Overwrite => text_editor_after_mut.texteditor.overwrite(*ch),
-        },
-
-        _ => (),
+/// Key hints for [`default`], built from the keys bound in `bindings`.
+pub fn default_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    bindings.hints(&locale::messages(), |_| true)
+}
+
+/// Keymap of the insert mode of vi editing (see [`QuerySelector::vi_mode`](preset::query_selector::QuerySelector::vi_mode)):
+/// <kbd>Esc</kbd> enters the normal mode and other keys work as in [`default`].
+pub fn vi_insert(
+    event: &Event,
+    renderer: &mut preset::query_selector::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return default(event, renderer);
+    };
+    if renderer.bindings.is_pending() {
+        return default(event, renderer);
     }
+    match vi.insert(key, renderer.text_editor_snapshot.after_mut()) {
+        Outcome::Unhandled => default(event, renderer),
+        outcome => {
+            renderer.kill_ring.interrupt();
+            switch_vi_mode(outcome, renderer);
+            Ok(PromptSignal::Continue)
+        }
+    }
+}
+
+/// Keymap of the normal mode of vi editing (see [`vi`](crate::text_editor::vi)).
+/// Keys bound to actions other than editing text (e.g. <kbd>Enter</kbd>) work as in [`default`].
+pub fn vi_normal(
+    event: &Event,
+    renderer: &mut preset::query_selector::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return Ok(PromptSignal::Continue);
+    };
+    if renderer.bindings.is_pending()
+        || renderer
+            .bindings
+            .get(key)
+            .is_some_and(|action| !action.edits_text())
+    {
+        return default(event, renderer);
+    }
+    let outcome = match vi.mode() {
+        ViMode::Visual => vi.visual(key, renderer.text_editor_snapshot.after_mut()),
+        _ => vi.normal(key, renderer.text_editor_snapshot.after_mut()),
+    };
+    renderer.kill_ring.interrupt();
+    switch_vi_mode(outcome, renderer);
     Ok(PromptSignal::Continue)
 }
+
+/// Keymap of the visual mode of vi editing, which shares [`vi_normal`].
+pub fn vi_visual(
+    event: &Event,
+    renderer: &mut preset::query_selector::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    vi_normal(event, renderer)
+}
+
+/// Activates the keymap of the vi mode entered by a key.
+fn switch_vi_mode(outcome: Outcome, renderer: &mut preset::query_selector::render::Renderer) {
+    if let Outcome::Switched(mode) = outcome {
+        renderer.keymap.borrow_mut().switch(mode.keymap_name());
+    }
+}
+
+/// Key hints for [`vi_insert`], built from the keys bound in `bindings`.
+pub fn vi_insert_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    vi::insert_hints(default_hints(bindings))
+}
+
+/// Key hints for [`vi_normal`], followed by the keys bound in `bindings`
+/// to actions other than editing text.
+pub fn vi_normal_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    let mut hints = vi::normal_hints();
+    hints.extend(bindings.hints(&locale::messages(), |action| !action.edits_text()));
+    hints
+}
+
+/// Key hints for [`vi_visual`].
+pub fn vi_visual_hints() -> Vec<KeyHint> {
+    vi::visual_hints()
+}
@@ -1,17 +1,29 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    time::Duration,
+};
 
 use crate::{
+    binding::Bindings,
     crossterm::event::Event,
+    grapheme::StyledGraphemes,
+    help, keyhint,
     listbox::{self, Listbox},
     pane::Pane,
+    preset::footer,
     snapshot::Snapshot,
     switch::ActiveKeySwitcher,
-    text, text_editor, PaneFactory, PromptSignal,
+    text,
+    text_editor::{self, vi::Vi, Clipboard, KillRing},
+    PaneFactory, PromptSignal,
 };
 
 /// Used to process and filter a list of options
 /// based on the input text in the `QuerySelector` component.
-pub type Filter = fn(&str, &Vec<String>) -> Vec<String>;
+/// It can be a closure, e.g. one capturing a search index.
+pub type Filter = Box<dyn Fn(&str, &Vec<String>) -> Vec<String>>;
 
 use super::keymap;
 
@@ -21,13 +33,25 @@ use super::keymap;
 pub struct Renderer {
     /// Manages key mappings specific to this renderer.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// Keys and the actions they perform in the default keymap.
+    pub bindings: Bindings,
     /// Snapshot of the title renderer.
     pub title_snapshot: Snapshot<text::State>,
     /// Snapshot of the text editor renderer.
     pub text_editor_snapshot: Snapshot<text_editor::State>,
+    /// State of vi editing, if enabled.
+    pub vi: Option<Vi>,
+    /// Text erased by the keymap, which can be inserted again.
+    pub kill_ring: KillRing,
+    /// Text copied by the keymap, which can be pasted.
+    pub clipboard: Clipboard,
     /// Snapshot of the listbox renderer.
     pub listbox_snapshot: Snapshot<listbox::State>,
     pub filter: Filter,
+    /// Optional snapshot of the footer renderer.
+    pub footer_snapshot: Option<Snapshot<keyhint::State>>,
+    /// Optional snapshot of the help overlay renderer.
+    pub help_snapshot: Option<Snapshot<help::State>>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -40,15 +64,27 @@ impl crate::Finalizer for Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
+        if let Some(help_snapshot) = &self.help_snapshot {
+            if help_snapshot.after().visible {
+                return vec![help_snapshot.create_pane(width, height)];
+            }
+        }
+        let mut panes = vec![
             self.title_snapshot.create_pane(width, height),
             self.text_editor_snapshot.create_pane(width, height),
             self.listbox_snapshot.create_pane(width, height),
-        ]
+        ];
+        if let Some(footer_snapshot) = &self.footer_snapshot {
+            panes.push(footer_snapshot.create_pane(width, height));
+        }
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
-        let keymap = *self.keymap.borrow_mut().get();
+        if footer::evaluate_help(&mut self.help_snapshot, &self.keymap, event) {
+            return Ok(PromptSignal::Continue);
+        }
+        let keymap = Rc::clone(self.keymap.borrow().get());
         let signal = keymap(event, self);
         if self.text_editor_snapshot.after().texteditor.text()
             != self.text_editor_snapshot.borrow_before().texteditor.text()
@@ -60,19 +96,35 @@ impl crate::Renderer for Renderer {
                 .text_without_cursor()
                 .to_string();
 
-            let list = (self.filter)(
-                &query,
-                &self
-                    .listbox_snapshot
-                    .init()
-                    .listbox
-                    .items()
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect(),
-            );
-            self.listbox_snapshot.after_mut().listbox = Listbox::from_iter(list);
+            let items = self.listbox_snapshot.init().listbox.items().clone();
+            let list = (self.filter)(&query, &items.iter().map(|e| e.to_string()).collect());
+            // Look up the original items so that their styles (e.g. from ANSI items) are kept,
+            // by index so that duplicate items are all kept.
+            let mut indexes = HashMap::<String, VecDeque<usize>>::new();
+            for (i, item) in items.iter().enumerate() {
+                indexes.entry(item.to_string()).or_default().push_back(i);
+            }
+            self.listbox_snapshot.after_mut().listbox =
+                Listbox::from_iter(list.into_iter().map(|e| {
+                    match indexes.get_mut(&e).and_then(|indexes| indexes.pop_front()) {
+                        Some(i) => items[i].clone(),
+                        None => StyledGraphemes::from(e),
+                    }
+                }));
         }
+        footer::refresh(&mut self.footer_snapshot, &self.keymap, &self.bindings);
         signal
     }
+
+    fn take_copied_text(&mut self) -> Option<String> {
+        self.clipboard.take_unsent()
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        footer::tick_interval(&self.footer_snapshot, &self.bindings)
+    }
+
+    fn tick(&mut self) {
+        footer::refresh_pending(&mut self.footer_snapshot, &self.bindings);
+    }
 }
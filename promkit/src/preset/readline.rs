@@ -1,16 +1,27 @@
-use std::{cell::RefCell, collections::HashSet};
+use std::{cell::RefCell, collections::HashSet, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::ContentStyle,
+    },
+    help,
+    keyhint::{self, KeyHint},
     listbox::{self, Listbox},
+    locale,
     snapshot::Snapshot,
-    style::StyleBuilder,
     suggest::Suggest,
     switch::ActiveKeySwitcher,
     text,
-    text_editor::{self, History},
-    validate::{ErrorMessageGenerator, Validator, ValidatorManager},
-    Prompt,
+    text_editor::{
+        self,
+        vi::{Vi, ViMode},
+        History,
+    },
+    theme::default_theme,
+    validate::ValidatorManager,
+    Prompt, PromptSignal,
 };
 
 pub mod confirm;
@@ -18,68 +29,90 @@ pub mod keymap;
 pub mod password;
 pub mod render;
 
+/// Checks whether an input is complete, e.g. has no unclosed brackets or quotes.
+/// It can be a closure, e.g. one parsing the input with the grammar of a REPL.
+pub type Completeness = Box<dyn Fn(&str) -> bool>;
+
 /// `Readline` struct provides functionality
 /// for reading a single line of input from the user.
 /// It supports various configurations
 /// such as input masking, history, suggestions, and custom styles.
 pub struct Readline {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
+    /// Keys and the actions they perform while suggestions are shown.
+    suggest_bindings: Bindings,
     /// State for the title displayed above the input field.
     title_state: text::State,
     /// State for the text editor where user input is entered.
     text_editor_state: text_editor::State,
+    /// State of vi editing, if enabled with [`Self::vi_mode`].
+    vi: Option<Vi>,
     suggest: Option<Suggest>,
     suggest_state: listbox::State,
+    /// Whether suggestions are shown in a popup anchored to the word being completed.
+    suggest_popup: bool,
+    /// Optional check for whether the input is complete, set with [`Self::completeness`].
+    completeness: Option<Completeness>,
     /// Optional validator for input validation with custom error messages.
     validator: Option<ValidatorManager<str>>,
     /// State for displaying error messages based on input validation.
     error_message_state: text::State,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl Default for Readline {
     fn default() -> Self {
+        let theme = default_theme();
+        let messages = locale::messages();
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
-                .register("on_suggest", self::keymap::on_suggest),
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            )
+            .register("on_suggest", Rc::new(self::keymap::on_suggest)),
+            bindings: self::keymap::default_bindings(),
+            suggest_bindings: self::keymap::suggest_bindings(),
             title_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
             text_editor_state: text_editor::State {
-                texteditor: Default::default(),
-                history: Default::default(),
-                prefix: String::from("❯❯ "),
-                mask: Default::default(),
-                prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
-                active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
-                inactive_char_style: StyleBuilder::new().build(),
-                edit_mode: Default::default(),
+                prefix: messages.prefix,
+                prefix_style: theme.prefix_style,
+                active_char_style: theme.active_char_style,
+                inactive_char_style: theme.inactive_char_style,
+                selection_style: theme.selection_style,
                 word_break_chars: HashSet::from([' ']),
-                lines: Default::default(),
+                ..Default::default()
             },
+            vi: Default::default(),
             suggest: Default::default(),
             suggest_state: listbox::State {
                 listbox: Listbox::from_iter(Vec::<String>::new()),
-                cursor: String::from("❯ "),
-                active_item_style: Some(
-                    StyleBuilder::new()
-                        .fgc(Color::DarkGrey)
-                        .bgc(Color::DarkYellow)
-                        .build(),
-                ),
-                inactive_item_style: Some(StyleBuilder::new().fgc(Color::DarkGrey).build()),
+                cursor: messages.cursor,
+                active_item_style: Some(theme.active_suggestion_style),
+                inactive_item_style: Some(theme.inactive_suggestion_style),
                 lines: Some(3),
+                wrap: Default::default(),
             },
+            suggest_popup: false,
+            completeness: Default::default(),
             validator: Default::default(),
             error_message_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .fgc(Color::DarkRed)
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.error_message_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
+            footer_state: Default::default(),
+            help_state: Default::default(),
         }
     }
 }
@@ -97,12 +130,28 @@ impl Readline {
         self
     }
 
+    /// Interprets markup tags (see [`crate::markup`]) in the title and error messages,
+    /// e.g. `"[bold]name[/]"`, instead of showing them as they are.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self.error_message_state.format = text::Format::Markup;
+        self
+    }
+
     /// Enables suggestion functionality with the provided `Suggest` instance.
     pub fn enable_suggest(mut self, suggest: Suggest) -> Self {
         self.suggest = Some(suggest);
         self
     }
 
+    /// Shows suggestions in a popup placed right below the start of the word being completed
+    /// and drawn on top of the panes under the input field,
+    /// instead of in a separate block under the input field.
+    pub fn enable_suggest_popup(mut self) -> Self {
+        self.suggest_popup = true;
+        self
+    }
+
     /// Enables history functionality allowing navigation through previous inputs.
     pub fn enable_history(mut self) -> Self {
         self.text_editor_state.history = Some(History::default());
@@ -115,6 +164,13 @@ impl Readline {
         self
     }
 
+    /// Sets the prefix string displayed before each line after the first of multi-line input,
+    /// such as `.. ` (see [`Self::completeness`]).
+    pub fn continuation_prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.text_editor_state.continuation_prefix = Some(prefix.as_ref().to_string());
+        self
+    }
+
     /// Sets the character used for masking input text, typically used for password fields.
     pub fn mask(mut self, mask: char) -> Self {
         self.text_editor_state.mask = Some(mask);
@@ -139,6 +195,19 @@ impl Readline {
         self
     }
 
+    /// Sets the highlighter that styles parts of the input as it is typed,
+    /// e.g. keywords and strings, instead of the style for inactive characters.
+    pub fn highlighter<H: text_editor::Highlighter + 'static>(mut self, highlighter: H) -> Self {
+        self.text_editor_state.highlighter = Some(Rc::new(highlighter));
+        self
+    }
+
+    /// Sets the style for the characters selected in the input field.
+    pub fn selection_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.selection_style = style;
+        self
+    }
+
     /// Sets the edit mode for the text editor, either insert or overwrite.
     pub fn edit_mode(mut self, mode: text_editor::Mode) -> Self {
         self.text_editor_state.edit_mode = mode;
@@ -157,17 +226,132 @@ impl Readline {
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
-        self.keymap = self.keymap.register(key, handler);
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
         self
     }
 
-    /// Configures a validator for the input with a function to validate the input and another to configure the error message.
-    pub fn validator(
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Binds `key` to `action` while suggestions are shown (see [`keymap::suggest_bindings`]).
+    pub fn bind_suggest<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.suggest_bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` while suggestions are shown,
+    /// so that it accepts the selected suggestion.
+    pub fn unbind_suggest<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.suggest_bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users over the bindings while suggestions are shown.
+    pub fn merge_suggest_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.suggest_bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
         mut self,
-        validator: Validator<str>,
-        error_message_generator: ErrorMessageGenerator<str>,
+        key: K,
+        hints: I,
     ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables modal editing in the style of vi (see [`text_editor::vi`]).
+    /// The prompt starts in the insert mode, and the current mode is shown before the prefix.
+    pub fn vi_mode(mut self) -> Self {
+        self.vi = Some(Vi::default());
+        self.keymap = self
+            .keymap
+            .register(
+                "vi_insert",
+                Rc::new(self::keymap::vi_insert) as keymap::Keymap,
+            )
+            .register("vi_normal", Rc::new(self::keymap::vi_normal))
+            .register("vi_visual", Rc::new(self::keymap::vi_visual))
+            .register_hints("vi_visual", self::keymap::vi_visual_hints());
+        self.keymap.switch(ViMode::Insert.keymap_name());
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>F1</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
+        self
+    }
+
+    /// Configures a check for whether the input is complete, called when submitting it.
+    /// If it returns `false`, e.g. for an unclosed bracket or quote,
+    /// a line break is inserted at the end of the input instead as in the Python REPL.
+    pub fn completeness<F>(mut self, completeness: F) -> Self
+    where
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.completeness = Some(Box::new(completeness));
+        self
+    }
+
+    /// Configures a validator for the input with a function to validate the input and another to configure the error message.
+    pub fn validator<V, E>(mut self, validator: V, error_message_generator: E) -> Self
+    where
+        V: Fn(&str) -> bool + 'static,
+        E: Fn(&str) -> String + 'static,
+    {
         self.validator = Some(ValidatorManager::new(validator, error_message_generator));
         self
     }
@@ -176,15 +360,55 @@ impl Readline {
     /// displaying the configured UI elements and handling user input.
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
         Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_snapshot: Snapshot::<text::State>::new(self.title_state),
-                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
-                suggest: self.suggest,
-                suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
-                validator: self.validator,
-                error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
-            },
+            renderer: self.renderer(),
         })
     }
+
+    /// Builds the renderer of the prompt, with the hints of the keymaps registered.
+    fn renderer(mut self) -> render::Renderer {
+        if let Some(vi) = &self.vi {
+            vi.show_indicator(&mut self.text_editor_state);
+        }
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings))
+            .register_hints_if_absent(
+                "on_suggest",
+                self::keymap::on_suggest_hints(&self.suggest_bindings),
+            );
+        if self.vi.is_some() {
+            self.keymap = self
+                .keymap
+                .register_hints_if_absent(
+                    "vi_insert",
+                    self::keymap::vi_insert_hints(&self.bindings),
+                )
+                .register_hints_if_absent(
+                    "vi_normal",
+                    self::keymap::vi_normal_hints(&self.bindings),
+                );
+        }
+        let keymap_hints = self.keymap.hints().to_vec();
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            bindings: self.bindings,
+            suggest_bindings: self.suggest_bindings,
+            title_snapshot: Snapshot::<text::State>::new(self.title_state),
+            text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+            vi: self.vi,
+            kill_ring: Default::default(),
+            clipboard: Default::default(),
+            suggest: self.suggest,
+            suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
+            suggest_popup: self.suggest_popup,
+            completeness: self.completeness,
+            validator: self.validator,
+            error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
+            footer_snapshot: self.footer_state.map(|mut state| {
+                state.hints = keymap_hints;
+                Snapshot::<keyhint::State>::new(state)
+            }),
+            help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
+        }
+    }
 }
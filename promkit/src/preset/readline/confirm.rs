@@ -1,4 +1,4 @@
-use crate::Prompt;
+use crate::{locale, Prompt};
 
 use super::{render, Readline};
 
@@ -10,22 +10,21 @@ impl Confirm {
     /// The prompt text is formatted
     /// to include "(y/n)" to indicate the expected input.
     ///
+    /// The suffix, the accepted answers and the error message
+    /// are taken from the message catalog (see [`crate::locale`]),
+    /// and [`locale::Messages::answer`] turns the result into a `bool`.
+    ///
     /// # Arguments
     ///
     /// * `text` - The text to display as part of the confirmation prompt.
     pub fn new<T: AsRef<str>>(text: T) -> Self {
-        Self(
-            Readline::default()
-                .prefix(format!("{} (y/n) ", text.as_ref()))
-                .validator(
-                    |text| -> bool {
-                        ["yes", "no", "y", "n", "Y", "N"]
-                            .iter()
-                            .any(|yn| *yn == text)
-                    },
-                    |_| String::from("Please type 'y' or 'n' as an answer"),
-                ),
-        )
+        let messages = locale::messages();
+        let prefix = format!("{} {} ", text.as_ref(), messages.confirm_suffix);
+        let error = messages.confirm_error.clone();
+        Self(Readline::default().prefix(prefix).validator(
+            move |text| messages.answer(text).is_some(),
+            move |_| error.clone(),
+        ))
     }
 
     /// Displays the confirmation prompt and waits for user input.
@@ -1,19 +1,25 @@
+use std::rc::Rc;
+
 use crate::{
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    binding::{self, Action, Bindings, KeyPress},
+    crossterm::event::{Event, KeyCode},
+    keyhint::KeyHint,
     listbox::Listbox,
-    preset, text_editor, PromptSignal,
+    locale, preset,
+    text_editor::vi::{self, Outcome, ViMode},
+    PromptSignal,
 };
 
-pub type Keymap = fn(
-    event: &Event,
-    renderer: &mut preset::readline::render::Renderer,
-) -> anyhow::Result<PromptSignal>;
+/// A handler of events, which can be a closure capturing the context of the application.
+/// It is reference-counted so that the renderer can call it while being borrowed mutably.
+pub type Keymap =
+    Rc<dyn Fn(&Event, &mut preset::readline::render::Renderer) -> anyhow::Result<PromptSignal>>;
 
-/// Default key bindings for the text editor.
+/// Default key bindings for the text editor, extending [`binding::text_editing`].
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
-/// | <kbd>Enter</kbd>       | Exit the editor if input is valid, otherwise show error message
+/// | <kbd>Enter</kbd>       | Exit the editor if input is valid, otherwise show error message; insert a line break if it is incomplete
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
 /// | <kbd>←</kbd>           | Move the cursor one character to the left
 /// | <kbd>→</kbd>           | Move the cursor one character to the right
@@ -23,30 +29,66 @@ pub type Keymap = fn(
 /// | <kbd>↓</kbd>           | Recall the next entry from history
 /// | <kbd>Backspace</kbd>   | Delete the character before the cursor
 /// | <kbd>Ctrl + U</kbd>    | Delete all characters in the current line
+/// | <kbd>Ctrl + Y</kbd>    | Insert the most recently erased text
+/// | <kbd>Alt + Y</kbd>     | Replace the text just inserted with the text erased before it
+/// | <kbd>Ctrl + Z</kbd>    | Undo the last edit, also with <kbd>Ctrl + _</kbd>
+/// | <kbd>Alt + Z</kbd>     | Redo the edit undone last
+/// | <kbd>Shift + ←/→</kbd> | Select the character to the left or right
+/// | <kbd>Shift + Home/End</kbd>| Select to the start or end of the input
+/// | <kbd>Alt + C</kbd>     | Copy the selection to the clipboard
+/// | <kbd>Alt + X</kbd>     | Cut the selection to the clipboard
+/// | <kbd>Alt + V</kbd>     | Paste from the clipboard
 /// | <kbd>Tab</kbd>         | Autocomplete the current input based on available suggestions
 /// | <kbd>Alt + B</kbd>     | Move the cursor to the previous nearest character within set (default: whitespace)
 /// | <kbd>Alt + F</kbd>     | Move the cursor to the next nearest character within set (default: whitespace)
 /// | <kbd>Ctrl + W</kbd>    | Erase to the previous nearest character within set (default: whitespace)
 /// | <kbd>Alt + D</kbd>     | Erase to the next nearest character within set (default: whitespace)
+pub fn default_bindings() -> Bindings {
+    let mut bindings = Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Enter), Action::Submit),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+        (KeyPress::plain(KeyCode::Tab), Action::Complete),
+        (KeyPress::plain(KeyCode::Up), Action::HistoryPrevious),
+        (KeyPress::plain(KeyCode::Down), Action::HistoryNext),
+    ]);
+    bindings.extend(binding::text_editing());
+    bindings
+}
+
+/// Performs the action bound to the key in [`Renderer::bindings`](preset::readline::render::Renderer::bindings)
+/// (see [`default_bindings`]), and inserts characters typed without a binding.
 pub fn default(
     event: &Event,
     renderer: &mut preset::readline::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    let action = renderer.bindings.lookup(event);
+    if !renderer.bindings.is_pending()
+        && !action.is_some_and(|action| {
+            action.kills() || matches!(action, Action::Yank | Action::YankPop)
+        })
+    {
+        renderer.kill_ring.interrupt();
+    }
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
     let error_message_after_mut = renderer.error_message_snapshot.after_mut();
     let suggest_after_mut = renderer.suggest_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
+    match action {
+        Some(Action::Submit) => {
             let text = text_editor_after_mut
                 .texteditor
                 .text_without_cursor()
                 .to_string();
+            if renderer
+                .completeness
+                .as_ref()
+                .is_some_and(|completeness| !completeness(&text))
+            {
+                // Continue on a new line after the whole input, wherever the cursor is.
+                text_editor_after_mut.texteditor.move_to_tail();
+                text_editor_after_mut.perform(Action::InsertNewline);
+                return Ok(PromptSignal::Continue);
+            }
             let valid = renderer
                 .validator
                 .as_ref()
@@ -69,19 +111,9 @@ pub fn default(
                 }
             };
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Tab,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
+        Some(Action::Complete) => {
             if let Some(suggest) = &renderer.suggest {
                 let text = text_editor_after_mut
                     .texteditor
@@ -98,186 +130,204 @@ pub fn default(
             }
         }
 
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Left,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.backward();
+        Some(action) => {
+            if !text_editor_after_mut.perform_with_clipboard(action, &mut renderer.clipboard) {
+                text_editor_after_mut.perform_with_kill_ring(action, &mut renderer.kill_ring);
+            }
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Right,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.forward();
+
+        // Keys consumed as the first keys of a chord are not typed.
+        None if renderer.bindings.is_pending() => (),
+        None => {
+            if let Some(ch) = binding::input_char(event) {
+                text_editor_after_mut.input(ch);
+            }
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('a'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_head(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('e'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_tail(),
+    }
+    Ok(PromptSignal::Continue)
+}
 
-        // Move cursor to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('b'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .move_to_previous_nearest(&text_editor_after_mut.word_break_chars),
+/// Key hints for [`default`], built from the keys bound in `bindings`.
+pub fn default_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    bindings.hints(&locale::messages(), |_| true)
+}
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('f'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .move_to_next_nearest(&text_editor_after_mut.word_break_chars),
+/// Keymap of the insert mode of vi editing (see [`Readline::vi_mode`](preset::readline::Readline::vi_mode)):
+/// <kbd>Esc</kbd> enters the normal mode and other keys work as in [`default`].
+pub fn vi_insert(
+    event: &Event,
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return default(event, renderer);
+    };
+    if renderer.bindings.is_pending() {
+        return default(event, renderer);
+    }
+    match vi.insert(key, renderer.text_editor_snapshot.after_mut()) {
+        Outcome::Unhandled => default(event, renderer),
+        outcome => {
+            renderer.kill_ring.interrupt();
+            switch_vi_mode(outcome, renderer);
+            Ok(PromptSignal::Continue)
+        }
+    }
+}
 
-        // Erase char(s).
-        Event::Key(KeyEvent {
-            code: KeyCode::Backspace,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('u'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase_all(),
+/// Keymap of the normal mode of vi editing (see [`vi`](crate::text_editor::vi)).
+/// Keys bound to actions other than editing text (e.g. <kbd>Enter</kbd>) work as in [`default`].
+pub fn vi_normal(
+    event: &Event,
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let (Some(vi), Some(key)) = (&mut renderer.vi, binding::pressed_key(event)) else {
+        return Ok(PromptSignal::Continue);
+    };
+    if renderer.bindings.is_pending()
+        || renderer
+            .bindings
+            .get(key)
+            .is_some_and(|action| !action.edits_text())
+    {
+        return default(event, renderer);
+    }
+    let outcome = match vi.mode() {
+        ViMode::Visual => vi.visual(key, renderer.text_editor_snapshot.after_mut()),
+        _ => vi.normal(key, renderer.text_editor_snapshot.after_mut()),
+    };
+    renderer.kill_ring.interrupt();
+    switch_vi_mode(outcome, renderer);
+    Ok(PromptSignal::Continue)
+}
 
-        // Erase to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('w'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .erase_to_previous_nearest(&text_editor_after_mut.word_break_chars),
+/// Keymap of the visual mode of vi editing, which shares [`vi_normal`].
+pub fn vi_visual(
+    event: &Event,
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    vi_normal(event, renderer)
+}
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('d'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .erase_to_next_nearest(&text_editor_after_mut.word_break_chars),
+/// Activates the keymap of the vi mode entered by a key.
+fn switch_vi_mode(outcome: Outcome, renderer: &mut preset::readline::render::Renderer) {
+    if let Outcome::Switched(mode) = outcome {
+        renderer.keymap.borrow_mut().switch(mode.keymap_name());
+    }
+}
 
-        // Choose history
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            if let Some(ref mut history) = &mut text_editor_after_mut.history {
-                if history.backward() {
-                    text_editor_after_mut.texteditor.replace(&history.get())
-                }
-            }
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            if let Some(ref mut history) = &mut text_editor_after_mut.history {
-                if history.forward() {
-                    text_editor_after_mut.texteditor.replace(&history.get())
-                }
-            }
-        }
+/// Key hints for [`vi_insert`], built from the keys bound in `bindings`.
+pub fn vi_insert_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    vi::insert_hints(default_hints(bindings))
+}
 
-        // Input char.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Key(KeyEvent {
-            code: KeyCode::Char(ch),
-            modifiers: KeyModifiers::SHIFT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => match text_editor_after_mut.edit_mode {
-            text_editor::Mode::Insert => text_editor_after_mut.texteditor.insert(*ch),
-            text_editor::Mode::Overwrite => text_editor_after_mut.texteditor.overwrite(*ch),
-        },
+/// Key hints for [`vi_normal`], followed by the keys bound in `bindings`
+/// to actions other than editing text.
+pub fn vi_normal_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    let mut hints = vi::normal_hints();
+    hints.extend(bindings.hints(&locale::messages(), |action| !action.edits_text()));
+    hints
+}
 
-        _ => (),
-    }
-    Ok(PromptSignal::Continue)
+/// Key hints for [`vi_visual`].
+pub fn vi_visual_hints() -> Vec<KeyHint> {
+    vi::visual_hints()
 }
 
+/// Key bindings while suggestions are shown.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Tab</kbd>, <kbd>↓</kbd> | Select the next suggestion
+/// | <kbd>↑</kbd>           | Select the previous suggestion
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | Others                 | Accept the selected suggestion
+pub fn suggest_bindings() -> Bindings {
+    Bindings::from_iter([
+        (KeyPress::plain(KeyCode::Tab), Action::SuggestNext),
+        (KeyPress::plain(KeyCode::Down), Action::SuggestNext),
+        (KeyPress::plain(KeyCode::Up), Action::SuggestPrevious),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+    ])
+}
+
+/// Performs the action bound to the key in [`Renderer::suggest_bindings`](preset::readline::render::Renderer::suggest_bindings)
+/// (see [`suggest_bindings`]), and accepts the selected suggestion on other keys.
 pub fn on_suggest(
     event: &Event,
     renderer: &mut preset::readline::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    let action = renderer.suggest_bindings.lookup(event);
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
     let suggest_after_mut = renderer.suggest_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+    match action {
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Tab,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
+        Some(Action::SuggestNext) => {
             suggest_after_mut.listbox.forward();
             text_editor_after_mut
                 .texteditor
                 .replace(&suggest_after_mut.listbox.get().to_string());
         }
 
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
+        Some(Action::SuggestPrevious) => {
             suggest_after_mut.listbox.backward();
             text_editor_after_mut
                 .texteditor
                 .replace(&suggest_after_mut.listbox.get().to_string());
         }
 
+        // Keys consumed as the first keys of a chord do not accept the suggestion yet.
+        None if renderer.suggest_bindings.is_pending() => (),
         _ => {
             suggest_after_mut.listbox = Listbox::from_iter(Vec::<String>::new());
 
-            renderer.keymap.borrow_mut().switch("default");
+            let keymap = renderer
+                .vi
+                .as_ref()
+                .map_or("default", |vi| vi.mode().keymap_name());
+            renderer.keymap.borrow_mut().switch(keymap);
         }
     }
     Ok(PromptSignal::Continue)
 }
+
+/// Key hints for [`on_suggest`], built from the keys bound in `bindings`.
+pub fn on_suggest_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    let messages = locale::messages();
+    let mut hints = bindings.hints(&messages, |_| true);
+    hints.push(KeyHint::new("Any", &messages.hint_accept));
+    hints
+}
+
+#[cfg(test)]
+mod test {
+    mod default {
+        use crate::{
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            preset::readline::{keymap, Readline},
+            text_editor::TextEditor,
+            PromptSignal,
+        };
+
+        #[test]
+        fn test_incomplete_input() {
+            let renderer = &mut Readline::default()
+                .completeness(|text| text.ends_with(')'))
+                .renderer();
+            let mut texteditor = TextEditor::new("foo(bar");
+            texteditor.move_to_head();
+            renderer.text_editor_snapshot.after_mut().texteditor = texteditor;
+
+            let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+            assert!(keymap::default(&enter, renderer).unwrap() == PromptSignal::Continue);
+            let texteditor = &renderer.text_editor_snapshot.after().texteditor;
+            assert_eq!("foo(bar\n", texteditor.text_without_cursor().to_string());
+            assert_eq!(8, texteditor.position());
+
+            let close = Event::Key(KeyEvent::new(KeyCode::Char(')'), KeyModifiers::NONE));
+            keymap::default(&close, renderer).unwrap();
+            assert!(keymap::default(&enter, renderer).unwrap() == PromptSignal::Quit);
+        }
+    }
+}
@@ -1,8 +1,4 @@
-use crate::{
-    crossterm::style::ContentStyle,
-    validate::{ErrorMessageGenerator, Validator},
-    Prompt,
-};
+use crate::{crossterm::style::ContentStyle, locale, Prompt};
 
 use super::{render, Readline};
 
@@ -11,9 +7,10 @@ use super::{render, Readline};
 pub struct Password(Readline);
 
 impl Default for Password {
-    /// Creates a new `Password` instance with default settings, using '*' as the mask character.
+    /// Creates a new `Password` instance with default settings,
+    /// using the mask character of the message catalog (see [`crate::locale`]), '*' by default.
     fn default() -> Self {
-        Self(Readline::default().mask('*'))
+        Self(Readline::default().mask(locale::messages().password_mask))
     }
 }
 
@@ -54,12 +51,24 @@ impl Password {
         self
     }
 
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self = Password(self.0.enable_footer());
+        self
+    }
+
+    /// Enables the help overlay listing every binding of the currently active keymap.
+    pub fn enable_help(mut self) -> Self {
+        self = Password(self.0.enable_help());
+        self
+    }
+
     /// Configures a validator for the password input with a function to validate the input and another to configure the error message.
-    pub fn validator(
-        mut self,
-        validator: Validator<str>,
-        error_message_generator: ErrorMessageGenerator<str>,
-    ) -> Self {
+    pub fn validator<V, E>(mut self, validator: V, error_message_generator: E) -> Self
+    where
+        V: Fn(&str) -> bool + 'static,
+        E: Fn(&str) -> String + 'static,
+    {
         self = Password(self.0.validator(validator, error_message_generator));
         self
     }
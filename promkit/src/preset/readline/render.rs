@@ -1,30 +1,57 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, snapshot::Snapshot, suggest::Suggest,
-    switch::ActiveKeySwitcher, text, text_editor, validate::ValidatorManager, PaneFactory,
-    PromptSignal,
+    binding::Bindings,
+    crossterm::event::Event,
+    help, keyhint, listbox,
+    pane::Pane,
+    preset::footer,
+    snapshot::Snapshot,
+    suggest::Suggest,
+    switch::ActiveKeySwitcher,
+    text,
+    text_editor::{self, vi::Vi, Clipboard, KillRing},
+    validate::ValidatorManager,
+    PaneFactory, PromptSignal,
 };
 
-use super::keymap;
+use super::{keymap, Completeness};
 
 /// A `Renderer` for the readline preset, responsible for managing the rendering process.
 /// It holds references to various components and their states, facilitating the rendering of the readline interface.
 pub struct Renderer {
     /// Manages key bindings and their associated actions within the readline interface.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// Keys and the actions they perform in the default keymap.
+    pub bindings: Bindings,
+    /// Keys and the actions they perform while suggestions are shown.
+    pub suggest_bindings: Bindings,
     /// Holds a snapshot of the title's renderer state, used for rendering the title section.
     pub title_snapshot: Snapshot<text::State>,
     /// Holds a snapshot of the text editor's renderer state, used for rendering the text input area.
     pub text_editor_snapshot: Snapshot<text_editor::State>,
+    /// State of vi editing, if enabled.
+    pub vi: Option<Vi>,
+    /// Text erased by the keymap, which can be inserted again.
+    pub kill_ring: KillRing,
+    /// Text copied by the keymap, which can be pasted.
+    pub clipboard: Clipboard,
     /// Optional suggest component for autocomplete functionality.
     pub suggest: Option<Suggest>,
     /// Holds a snapshot of the suggest box's renderer state, used when rendering suggestions for autocomplete.
     pub suggest_snapshot: Snapshot<listbox::State>,
+    /// Whether the suggest box is drawn as a popup anchored to the word being completed in the text editor.
+    pub suggest_popup: bool,
+    /// Optional check for whether the input is complete, which continues it on a new line otherwise.
+    pub completeness: Option<Completeness>,
     /// Optional validator manager for input validation.
     pub validator: Option<ValidatorManager<str>>,
     /// Holds a snapshot of the error message's renderer state, used for rendering error messages.
     pub error_message_snapshot: Snapshot<text::State>,
+    /// Optional snapshot of the footer's renderer state, used for rendering key hints.
+    pub footer_snapshot: Option<Snapshot<keyhint::State>>,
+    /// Optional snapshot of the help overlay renderer.
+    pub help_snapshot: Option<Snapshot<help::State>>,
 }
 
 impl crate::Finalizer for Renderer {
@@ -42,16 +69,59 @@ impl crate::Finalizer for Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
+        if let Some(help_snapshot) = &self.help_snapshot {
+            if help_snapshot.after().visible {
+                return vec![help_snapshot.create_pane(width, height)];
+            }
+        }
+        let mut panes = vec![
             self.title_snapshot.create_pane(width, height),
             self.error_message_snapshot.create_pane(width, height),
-            self.text_editor_snapshot.create_pane(width, height),
-            self.suggest_snapshot.create_pane(width, height),
-        ]
+        ];
+        if self.suggest_popup {
+            // The popup is drawn over the panes below the text editor,
+            // so they are merged into a single pane first.
+            let mut pane = self.text_editor_snapshot.create_pane(width, height);
+            if let Some(footer_snapshot) = &self.footer_snapshot {
+                pane = pane.join(&footer_snapshot.create_pane(width, height));
+            }
+            let popup = self.suggest_snapshot.create_pane(width, height);
+            // Anchored to the word being completed, not the cursor moving as it is typed.
+            let (row, column) = self
+                .text_editor_snapshot
+                .after()
+                .word_coordinate(width, height);
+            let column = column.min((width as usize).saturating_sub(popup.widths()));
+            panes.push(pane.overlay(&popup, row + 1, column));
+            return panes;
+        }
+        panes.push(self.text_editor_snapshot.create_pane(width, height));
+        panes.push(self.suggest_snapshot.create_pane(width, height));
+        if let Some(footer_snapshot) = &self.footer_snapshot {
+            panes.push(footer_snapshot.create_pane(width, height));
+        }
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
-        let keymap = *self.keymap.borrow_mut().get();
-        keymap(event, self)
+        if footer::evaluate_help(&mut self.help_snapshot, &self.keymap, event) {
+            return Ok(PromptSignal::Continue);
+        }
+        let keymap = Rc::clone(self.keymap.borrow().get());
+        let signal = keymap(event, self);
+        footer::refresh(&mut self.footer_snapshot, &self.keymap, &self.bindings);
+        signal
+    }
+
+    fn take_copied_text(&mut self) -> Option<String> {
+        self.clipboard.take_unsent()
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        footer::tick_interval(&self.footer_snapshot, &self.bindings)
+    }
+
+    fn tick(&mut self) {
+        footer::refresh_pending(&mut self.footer_snapshot, &self.bindings);
     }
 }
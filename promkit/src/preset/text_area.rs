@@ -0,0 +1,292 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc, time::Duration};
+
+use crate::{
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::ContentStyle,
+    },
+    help,
+    keyhint::{self, KeyHint},
+    locale,
+    snapshot::Snapshot,
+    switch::ActiveKeySwitcher,
+    text, text_editor,
+    theme::default_theme,
+    validate::ValidatorManager,
+    Prompt, PromptSignal,
+};
+
+pub mod keymap;
+pub mod render;
+
+/// `TextArea` struct provides functionality for reading input of multiple lines
+/// from the user, such as commit messages, SQL queries or descriptions.
+/// <kbd>Enter</kbd> inserts a line break,
+/// and the input is submitted with <kbd>Ctrl + D</kbd> by default (see [`Self::submit_key`]).
+pub struct TextArea {
+    keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
+    /// State for the title displayed above the input field.
+    title_state: text::State,
+    /// State for the text editor where user input is entered.
+    text_editor_state: text_editor::State,
+    /// Optional validator for input validation with custom error messages.
+    validator: Option<ValidatorManager<str>>,
+    /// State for displaying error messages based on input validation.
+    error_message_state: text::State,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        let theme = default_theme();
+        let messages = locale::messages();
+        Self {
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
+            title_state: text::State {
+                text: Default::default(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
+            },
+            text_editor_state: text_editor::State {
+                prefix: messages.prefix,
+                prefix_style: theme.prefix_style,
+                active_char_style: theme.active_char_style,
+                inactive_char_style: theme.inactive_char_style,
+                selection_style: theme.selection_style,
+                word_break_chars: HashSet::from([' ', '\n']),
+                ..Default::default()
+            },
+            validator: Default::default(),
+            error_message_state: text::State {
+                text: Default::default(),
+                style: theme.error_message_style,
+                wrap: Default::default(),
+                format: Default::default(),
+            },
+            footer_state: Default::default(),
+            help_state: Default::default(),
+        }
+    }
+}
+
+impl TextArea {
+    /// Sets the title text displayed above the input field.
+    pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = text.as_ref().to_string();
+        self
+    }
+
+    /// Sets the style for the title text.
+    pub fn title_style(mut self, style: ContentStyle) -> Self {
+        self.title_state.style = style;
+        self
+    }
+
+    /// Interprets markup tags (see [`crate::markup`]) in the title and error messages,
+    /// e.g. `"[bold]name[/]"`, instead of showing them as they are.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self.error_message_state.format = text::Format::Markup;
+        self
+    }
+
+    /// Sets the text the input starts with, placing the cursor at its end.
+    pub fn text<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.text_editor_state.texteditor = text_editor::TextEditor::new(text);
+        self
+    }
+
+    /// Sets the prefix string displayed before the first line,
+    /// to which the other lines are aligned.
+    pub fn prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.text_editor_state.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    /// Sets the style for the prefix string.
+    pub fn prefix_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.prefix_style = style;
+        self
+    }
+
+    /// Sets the style for the currently active character in the input field.
+    pub fn active_char_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.active_char_style = style;
+        self
+    }
+
+    /// Sets the style for characters that are not currently active in the input field.
+    pub fn inactive_char_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.inactive_char_style = style;
+        self
+    }
+
+    /// Sets the highlighter that styles parts of the input as it is typed,
+    /// e.g. keywords and strings, instead of the style for inactive characters.
+    pub fn highlighter<H: text_editor::Highlighter + 'static>(mut self, highlighter: H) -> Self {
+        self.text_editor_state.highlighter = Some(Rc::new(highlighter));
+        self
+    }
+
+    /// Sets the style for the characters selected in the input field.
+    pub fn selection_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.selection_style = style;
+        self
+    }
+
+    /// Shows the number of each line before it.
+    pub fn line_numbers(mut self) -> Self {
+        self.text_editor_state.line_number_style = Some(default_theme().line_number_style);
+        self
+    }
+
+    /// Sets the style for the line numbers, showing them if necessary.
+    pub fn line_number_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.line_number_style = Some(style);
+        self
+    }
+
+    /// Sets the characters to be for word break.
+    pub fn word_break_chars(mut self, characters: HashSet<char>) -> Self {
+        self.text_editor_state.word_break_chars = characters;
+        self
+    }
+
+    /// Sets the number of rows available for rendering the input,
+    /// which scrolls to keep the cursor visible when it is longer.
+    pub fn text_editor_lines(mut self, lines: usize) -> Self {
+        self.text_editor_state.lines = Some(lines);
+        self
+    }
+
+    /// Sets the key that submits the input instead of <kbd>Ctrl + D</kbd>,
+    /// e.g. <kbd>Alt + Enter</kbd>.
+    pub fn submit_key<K: Into<Chord>>(mut self, key: K) -> Self {
+        let key = key.into();
+        for bound in self.bindings.keys(Action::Submit) {
+            self.bindings.unbind(bound);
+        }
+        self.bindings.bind(key, Action::Submit);
+        self
+    }
+
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>F1</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
+        self
+    }
+
+    /// Configures a validator for the input with a function to validate the input and another to configure the error message.
+    pub fn validator<V, E>(mut self, validator: V, error_message_generator: E) -> Self
+    where
+        V: Fn(&str) -> bool + 'static,
+        E: Fn(&str) -> String + 'static,
+    {
+        self.validator = Some(ValidatorManager::new(validator, error_message_generator));
+        self
+    }
+
+    /// Initiates the prompt process,
+    /// displaying the configured UI elements and handling user input.
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        let keymap_hints = self.keymap.hints().to_vec();
+        Ok(Prompt {
+            renderer: render::Renderer {
+                keymap: RefCell::new(self.keymap),
+                bindings: self.bindings,
+                title_snapshot: Snapshot::<text::State>::new(self.title_state),
+                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+                kill_ring: Default::default(),
+                clipboard: Default::default(),
+                validator: self.validator,
+                error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
+                footer_snapshot: self.footer_state.map(|mut state| {
+                    state.hints = keymap_hints;
+                    Snapshot::<keyhint::State>::new(state)
+                }),
+                help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
+            },
+        })
+    }
+}
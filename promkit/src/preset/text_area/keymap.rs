@@ -0,0 +1,146 @@
+use std::rc::Rc;
+
+use crate::{
+    binding::{self, Action, Bindings, KeyPress},
+    crossterm::{
+        event::{Event, KeyCode, KeyModifiers},
+        terminal,
+    },
+    keyhint::KeyHint,
+    locale, preset, PromptSignal,
+};
+
+/// A handler of events, which can be a closure capturing the context of the application.
+/// It is reference-counted so that the renderer can call it while being borrowed mutably.
+pub type Keymap =
+    Rc<dyn Fn(&Event, &mut preset::text_area::render::Renderer) -> anyhow::Result<PromptSignal>>;
+
+/// Default key bindings for the text area, extending [`binding::text_editing`].
+///
+/// | Key                        | Action
+/// | :------------------------- | :-------------------------------------------
+/// | <kbd>Ctrl + D</kbd>        | Exit the editor if input is valid, otherwise show error message
+/// | <kbd>Ctrl + C</kbd>        | Interrupt the current operation
+/// | <kbd>Enter</kbd>           | Insert a line break
+/// | <kbd>←</kbd>               | Move the cursor one character to the left
+/// | <kbd>→</kbd>               | Move the cursor one character to the right
+/// | <kbd>↑</kbd>               | Move the cursor to the row above, keeping the column
+/// | <kbd>↓</kbd>               | Move the cursor to the row below, keeping the column
+/// | <kbd>Alt + ↑</kbd>         | Move the cursor to the previous line, skipping its wrapped rows
+/// | <kbd>Alt + ↓</kbd>         | Move the cursor to the next line, skipping its wrapped rows
+/// | <kbd>Ctrl + A</kbd>        | Move the cursor to the start of the input
+/// | <kbd>Ctrl + E</kbd>        | Move the cursor to the end of the input
+/// | <kbd>Alt + B</kbd>         | Move the cursor to the previous nearest character within set (default: whitespace)
+/// | <kbd>Alt + F</kbd>         | Move the cursor to the next nearest character within set (default: whitespace)
+/// | <kbd>Backspace</kbd>       | Delete the character before the cursor
+/// | <kbd>Delete</kbd>          | Delete the character under the cursor
+/// | <kbd>Ctrl + U</kbd>        | Delete all characters
+/// | <kbd>Ctrl + W</kbd>        | Erase to the previous nearest character within set (default: whitespace)
+/// | <kbd>Alt + D</kbd>         | Erase to the next nearest character within set (default: whitespace)
+/// | <kbd>Ctrl + Y</kbd>        | Insert the most recently erased text
+/// | <kbd>Alt + Y</kbd>         | Replace the text just inserted with the text erased before it
+/// | <kbd>Ctrl + Z</kbd>        | Undo the last edit, also with <kbd>Ctrl + _</kbd>
+/// | <kbd>Alt + Z</kbd>         | Redo the edit undone last
+/// | <kbd>Shift + ←/→</kbd>     | Select the character to the left or right
+/// | <kbd>Shift + Home/End</kbd>| Select to the start or end of the input
+/// | <kbd>Alt + C</kbd>         | Copy the selection to the clipboard
+/// | <kbd>Alt + X</kbd>         | Cut the selection to the clipboard
+/// | <kbd>Alt + V</kbd>         | Paste from the clipboard
+pub fn default_bindings() -> Bindings {
+    let mut bindings = Bindings::from_iter([
+        (KeyPress::ctrl('d'), Action::Submit),
+        (KeyPress::ctrl('c'), Action::Interrupt),
+        (KeyPress::plain(KeyCode::Enter), Action::InsertNewline),
+        (KeyPress::plain(KeyCode::Up), Action::MoveUp),
+        (KeyPress::plain(KeyCode::Down), Action::MoveDown),
+        (
+            KeyPress::new(KeyCode::Up, KeyModifiers::ALT),
+            Action::MoveLineUp,
+        ),
+        (
+            KeyPress::new(KeyCode::Down, KeyModifiers::ALT),
+            Action::MoveLineDown,
+        ),
+        (KeyPress::plain(KeyCode::Delete), Action::EraseForward),
+    ]);
+    bindings.extend(binding::text_editing());
+    bindings
+}
+
+/// Performs the action bound to the key in [`Renderer::bindings`](preset::text_area::render::Renderer::bindings)
+/// (see [`default_bindings`]), and inserts characters typed without a binding.
+pub fn default(
+    event: &Event,
+    renderer: &mut preset::text_area::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let action = renderer.bindings.lookup(event);
+    if !renderer.bindings.is_pending()
+        && !action.is_some_and(|action| {
+            action.kills() || matches!(action, Action::Yank | Action::YankPop)
+        })
+    {
+        renderer.kill_ring.interrupt();
+    }
+    let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
+    let error_message_after_mut = renderer.error_message_snapshot.after_mut();
+
+    match action {
+        Some(Action::Submit) => {
+            let text = text_editor_after_mut
+                .texteditor
+                .text_without_cursor()
+                .to_string();
+            let valid = renderer
+                .validator
+                .as_ref()
+                .map(|validator| {
+                    let valid = validator.validate(&text);
+                    if !valid {
+                        error_message_after_mut.text = validator.generate_error_message(&text);
+                    }
+                    valid
+                })
+                .unwrap_or(true);
+            return if valid {
+                Ok(PromptSignal::Quit)
+            } else {
+                Ok(PromptSignal::Continue)
+            };
+        }
+        Some(Action::Interrupt) => return Err(anyhow::anyhow!("ctrl+c")),
+
+        // Rows depend on the width of the terminal, where the panes are as wide as it.
+        Some(Action::MoveUp) => {
+            text_editor_after_mut.move_up_visually(terminal_width());
+        }
+        Some(Action::MoveDown) => {
+            text_editor_after_mut.move_down_visually(terminal_width());
+        }
+
+        Some(action) => {
+            if !text_editor_after_mut.perform_with_clipboard(action, &mut renderer.clipboard) {
+                text_editor_after_mut.perform_with_kill_ring(action, &mut renderer.kill_ring);
+            }
+        }
+
+        // Keys consumed as the first keys of a chord are not typed.
+        None if renderer.bindings.is_pending() => (),
+        None => {
+            if let Some(ch) = binding::input_char(event) {
+                text_editor_after_mut.input(ch);
+            }
+        }
+    }
+    Ok(PromptSignal::Continue)
+}
+
+/// Returns the width of the terminal, or no limit if it is unknown,
+/// in which case the cursor moves by lines instead of rows.
+fn terminal_width() -> u16 {
+    terminal::size().map_or(u16::MAX, |(width, _)| width)
+}
+
+/// Key hints for [`default`], built from the keys bound in `bindings`.
+pub fn default_hints(bindings: &Bindings) -> Vec<KeyHint> {
+    bindings.hints(&locale::messages(), |_| true)
+}
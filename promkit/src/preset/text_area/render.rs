@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    binding::Bindings,
+    crossterm::event::Event,
+    help, keyhint,
+    pane::Pane,
+    preset::footer,
+    snapshot::Snapshot,
+    switch::ActiveKeySwitcher,
+    text,
+    text_editor::{self, Clipboard, KillRing},
+    validate::ValidatorManager,
+    PaneFactory, PromptSignal,
+};
+
+use super::keymap;
+
+/// A `Renderer` for the text area preset, responsible for managing the rendering process.
+pub struct Renderer {
+    /// Manages key bindings and their associated actions within the text area.
+    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// Keys and the actions they perform in the default keymap.
+    pub bindings: Bindings,
+    /// Holds a snapshot of the title's renderer state, used for rendering the title section.
+    pub title_snapshot: Snapshot<text::State>,
+    /// Holds a snapshot of the text editor's renderer state, used for rendering the lines of input.
+    pub text_editor_snapshot: Snapshot<text_editor::State>,
+    /// Text erased by the keymap, which can be inserted again.
+    pub kill_ring: KillRing,
+    /// Text copied by the keymap, which can be pasted.
+    pub clipboard: Clipboard,
+    /// Optional validator manager for input validation.
+    pub validator: Option<ValidatorManager<str>>,
+    /// Holds a snapshot of the error message's renderer state, used for rendering error messages.
+    pub error_message_snapshot: Snapshot<text::State>,
+    /// Optional snapshot of the footer's renderer state, used for rendering key hints.
+    pub footer_snapshot: Option<Snapshot<keyhint::State>>,
+    /// Optional snapshot of the help overlay renderer.
+    pub help_snapshot: Option<Snapshot<help::State>>,
+}
+
+impl crate::Finalizer for Renderer {
+    type Return = String;
+
+    fn finalize(&self) -> anyhow::Result<Self::Return> {
+        Ok(self
+            .text_editor_snapshot
+            .after()
+            .texteditor
+            .text_without_cursor()
+            .to_string())
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        if let Some(help_snapshot) = &self.help_snapshot {
+            if help_snapshot.after().visible {
+                return vec![help_snapshot.create_pane(width, height)];
+            }
+        }
+        let mut panes = vec![
+            self.title_snapshot.create_pane(width, height),
+            self.error_message_snapshot.create_pane(width, height),
+            self.text_editor_snapshot.create_pane(width, height),
+        ];
+        if let Some(footer_snapshot) = &self.footer_snapshot {
+            panes.push(footer_snapshot.create_pane(width, height));
+        }
+        panes
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        if footer::evaluate_help(&mut self.help_snapshot, &self.keymap, event) {
+            return Ok(PromptSignal::Continue);
+        }
+        let keymap = Rc::clone(self.keymap.borrow().get());
+        let signal = keymap(event, self);
+        footer::refresh(&mut self.footer_snapshot, &self.keymap, &self.bindings);
+        signal
+    }
+
+    fn take_copied_text(&mut self) -> Option<String> {
+        self.clipboard.take_unsent()
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        footer::tick_interval(&self.footer_snapshot, &self.bindings)
+    }
+
+    fn tick(&mut self) {
+        footer::refresh_pending(&mut self.footer_snapshot, &self.bindings);
+    }
+}
@@ -1,13 +1,20 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::{
-    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    binding::{Action, BindingOverrides, Bindings, Chord},
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        style::ContentStyle,
+    },
+    help,
+    keyhint::{self, KeyHint},
+    locale,
     snapshot::Snapshot,
-    style::StyleBuilder,
     switch::ActiveKeySwitcher,
     text,
+    theme::default_theme,
     tree::{self, Node},
-    Prompt,
+    Prompt, PromptSignal,
 };
 
 pub mod keymap;
@@ -17,10 +24,16 @@ pub mod render;
 /// and managing a hierarchical list of options.
 pub struct Tree {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys and the actions they perform in the default keymap.
+    bindings: Bindings,
     /// State for the title displayed above the tree.
     title_state: text::State,
     /// State for the tree itself.
     tree_state: tree::State,
+    /// Optional state for the footer showing key hints.
+    footer_state: Option<keyhint::State>,
+    /// Optional state for the help overlay listing key bindings.
+    help_state: Option<help::State>,
 }
 
 impl Tree {
@@ -30,20 +43,28 @@ impl Tree {
     ///
     /// * `root` - The root node of the tree.
     pub fn new(root: Node) -> Self {
+        let theme = default_theme();
+        let messages = locale::messages();
         Self {
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new(
+                "default",
+                Rc::new(self::keymap::default) as keymap::Keymap,
+            ),
+            bindings: self::keymap::default_bindings(),
+            footer_state: Default::default(),
+            help_state: Default::default(),
             title_state: text::State {
                 text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
+                style: theme.title_style,
+                wrap: Default::default(),
+                format: Default::default(),
             },
             tree_state: tree::State {
                 tree: tree::Tree::new(root),
-                folded_symbol: String::from("▶︎ "),
-                unfolded_symbol: String::from("▼ "),
-                active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
-                inactive_item_style: StyleBuilder::new().build(),
+                folded_symbol: messages.folded_symbol,
+                unfolded_symbol: messages.unfolded_symbol,
+                active_item_style: theme.active_item_style,
+                inactive_item_style: theme.inactive_item_style,
                 lines: Default::default(),
                 indent: 2,
             },
@@ -62,6 +83,13 @@ impl Tree {
         self
     }
 
+    /// Interprets markup tags (see [`crate::markup`]) in the title,
+    /// e.g. `"[bold]name[/]"`, instead of showing it as it is.
+    pub fn markup(mut self) -> Self {
+        self.title_state.format = text::Format::Markup;
+        self
+    }
+
     /// Sets the symbol used to indicate a folded (collapsed) node.
     pub fn folded_symbol<T: AsRef<str>>(mut self, symbol: T) -> Self {
         self.tree_state.folded_symbol = symbol.as_ref().to_string();
@@ -98,20 +126,98 @@ impl Tree {
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
-        self.keymap = self.keymap.register(key, handler);
+    /// Binds `key`, a single key or a chord, to `action` in the default keymap,
+    /// replacing the action previously bound to it (see [`keymap::default_bindings`]).
+    pub fn bind<K: Into<Chord>>(mut self, key: K, action: Action) -> Self {
+        self.bindings.bind(key, action);
+        self
+    }
+
+    /// Removes the binding of `key` from the default keymap.
+    pub fn unbind<K: Into<Chord>>(mut self, key: K) -> Self {
+        self.bindings.unbind(key);
+        self
+    }
+
+    /// Applies bindings configured by users, e.g. loaded with [`BindingsConfig`](crate::binding::BindingsConfig),
+    /// over the bindings of the default keymap.
+    pub fn merge_bindings(mut self, overrides: &BindingOverrides) -> Self {
+        self.bindings.merge(overrides);
+        self
+    }
+
+    /// Sets how long to wait for the next key of a chord bound in the default keymap.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.bindings.set_chord_timeout(timeout);
+        self
+    }
+
+    pub fn register_keymap<K, F>(mut self, key: K, handler: F) -> Self
+    where
+        K: AsRef<str>,
+        F: Fn(&Event, &mut render::Renderer) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.keymap = self.keymap.register(key, Rc::new(handler));
+        self
+    }
+
+    /// Attaches key hints to the keymap registered with the given key,
+    /// which are shown in the footer while the keymap is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.keymap = self.keymap.register_hints(key, hints);
+        self
+    }
+
+    /// Enables the footer that shows key hints for the currently active keymap.
+    pub fn enable_footer(mut self) -> Self {
+        self.footer_state = Some(keyhint::State::new(&default_theme()));
+        self
+    }
+
+    /// Enables the help overlay (see [`help::State`]), opened with <kbd>?</kbd> by default
+    /// (see [`Self::help_key`]).
+    pub fn enable_help(mut self) -> Self {
+        self.help_state = Some(help::State::new(
+            &default_theme(),
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        ));
+        self
+    }
+
+    /// Sets the key to open the help overlay, enabling it if necessary.
+    pub fn help_key(mut self, key: KeyEvent) -> Self {
+        if self.help_state.is_none() {
+            self = self.enable_help();
+        }
+        if let Some(state) = &mut self.help_state {
+            state.toggle_key = key;
+        }
         self
     }
 
     /// Displays the tree prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is a list of selected options.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+    pub fn prompt(mut self) -> anyhow::Result<Prompt<render::Renderer>> {
+        self.keymap = self
+            .keymap
+            .register_hints_if_absent("default", self::keymap::default_hints(&self.bindings));
+        let keymap_hints = self.keymap.hints().to_vec();
         Ok(Prompt {
             renderer: render::Renderer {
                 keymap: RefCell::new(self.keymap),
+                bindings: self.bindings,
                 title_snapshot: Snapshot::<text::State>::new(self.title_state),
                 tree_snapshot: Snapshot::<tree::State>::new(self.tree_state),
+                footer_snapshot: self.footer_state.map(|mut state| {
+                    state.hints = keymap_hints;
+                    Snapshot::<keyhint::State>::new(state)
+                }),
+                help_snapshot: self.help_state.map(Snapshot::<help::State>::new),
             },
         })
     }
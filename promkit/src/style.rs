@@ -1,4 +1,10 @@
-use crate::crossterm::style::{Attributes, Color, ContentStyle};
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::crossterm::style::{Attribute, Attributes, Color, ContentStyle};
 
 /// A struct for defining and building styles for terminal text.
 ///
@@ -89,6 +95,44 @@ impl StyleBuilder {
         self
     }
 
+    /// Parses a style string such as `"bold fg:#ff8800 bg:black underline"`.
+    ///
+    /// The string is made of whitespace separated words, each of which is one of:
+    /// - an attribute: `bold`, `dim`, `italic`, `underline`, `blink`, `reverse`, `hidden` or `strike`,
+    /// - `fg:`, `bg:` or `ul:` followed by a color for the foreground,
+    ///   background and underline respectively,
+    ///
+    /// where a color is a name such as `red` or `dark_grey`,
+    /// a hex code such as `#ff8800`, or an ANSI value from `0` to `255`.
+    /// An empty string is the default style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use promkit::{crossterm::style::Color, style::StyleBuilder};
+    ///
+    /// let style = StyleBuilder::parse("bold fg:dark_cyan").unwrap().build();
+    /// assert_eq!(Some(Color::DarkCyan), style.foreground_color);
+    /// assert!(StyleBuilder::parse("fg:dark_cyna").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseStyleError> {
+        let mut builder = StyleBuilder::new();
+        for word in s.split_whitespace() {
+            if let Some(color) = word.strip_prefix("fg:") {
+                builder = builder.fgc(parse_style_color(color)?);
+            } else if let Some(color) = word.strip_prefix("bg:") {
+                builder = builder.bgc(parse_style_color(color)?);
+            } else if let Some(color) = word.strip_prefix("ul:") {
+                builder = builder.ulc(parse_style_color(color)?);
+            } else if let Some(attr) = parse_attribute(word) {
+                builder.attributes.set(attr);
+            } else {
+                return Err(ParseStyleError::UnknownWord(word.to_string()));
+            }
+        }
+        Ok(builder)
+    }
+
     /// Builds and returns a `ContentStyle` based on the set properties.
     ///
     /// # Returns
@@ -103,3 +147,402 @@ impl StyleBuilder {
         }
     }
 }
+
+impl FromStr for StyleBuilder {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// An error returned by [`StyleBuilder::parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseStyleError {
+    /// A color that is neither a known name, a hex code nor an ANSI value.
+    UnknownColor(String),
+    /// A word that is neither an attribute nor a `fg:`, `bg:` or `ul:` color.
+    UnknownWord(String),
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownColor(color) => write!(
+                f,
+                "unknown color `{}`, expected a name such as `dark_cyan`, \
+                 a hex code such as `#ff8800` or a number from 0 to 255",
+                color
+            ),
+            Self::UnknownWord(word) => write!(
+                f,
+                "unknown style `{}`, expected an attribute such as `bold` \
+                 or a color such as `fg:red`, `bg:red` or `ul:red`",
+                word
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseStyleError {}
+
+/// Parses a color of a style string, which can also be an ANSI value (`0`-`255`).
+fn parse_style_color(word: &str) -> Result<Color, ParseStyleError> {
+    match word.parse::<u8>() {
+        Ok(value) => Ok(Color::AnsiValue(value)),
+        Err(_) => parse_color(word).ok_or_else(|| ParseStyleError::UnknownColor(word.to_string())),
+    }
+}
+
+/// Parses an attribute name, as used by style strings and [`crate::markup`].
+pub(crate) fn parse_attribute(word: &str) -> Option<Attribute> {
+    match word {
+        "bold" => Some(Attribute::Bold),
+        "dim" => Some(Attribute::Dim),
+        "italic" => Some(Attribute::Italic),
+        "underline" => Some(Attribute::Underlined),
+        "blink" => Some(Attribute::SlowBlink),
+        "reverse" => Some(Attribute::Reverse),
+        "hidden" => Some(Attribute::Hidden),
+        "strike" => Some(Attribute::CrossedOut),
+        _ => None,
+    }
+}
+
+/// Parses a color name or a hex code (`#rrggbb`), as used by style strings and [`crate::markup`].
+pub(crate) fn parse_color(word: &str) -> Option<Color> {
+    match word.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => {
+            let rgb = u32::from_str_radix(hex, 16).ok()?;
+            Some(Color::Rgb {
+                r: (rgb >> 16) as u8,
+                g: (rgb >> 8) as u8,
+                b: rgb as u8,
+            })
+        }
+        Some(_) => None,
+        None => Color::try_from(word).ok(),
+    }
+}
+
+/// The set of colors a terminal can display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colors, e.g. when `NO_COLOR` is set or `TERM` is `dumb`.
+    /// Text attributes such as bold are kept.
+    NoColor,
+    /// The 16 standard colors.
+    Ansi16,
+    /// The 256 colors of xterm.
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the color depth from the environment variables
+    /// `NO_COLOR`, `TERM`, `COLORTERM` and `WT_SESSION` (Windows Terminal).
+    pub fn detect() -> Self {
+        Self::detect_from(|key| std::env::var(key).ok())
+    }
+
+    fn detect_from<F: Fn(&str) -> Option<String>>(var: F) -> Self {
+        // https://no-color.org: any non-empty value disables colors.
+        if var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::NoColor;
+        }
+        let term = var("TERM").unwrap_or_default();
+        if term == "dumb" {
+            return Self::NoColor;
+        }
+        if matches!(
+            var("COLORTERM").as_deref(),
+            Some("truecolor") | Some("24bit")
+        ) || var("WT_SESSION").is_some()
+        {
+            return Self::TrueColor;
+        }
+        if term.contains("256color") {
+            return Self::Ansi256;
+        }
+        Self::Ansi16
+    }
+
+    /// Maps the colors of `style` to the nearest ones this depth supports,
+    /// or removes them for [`ColorDepth::NoColor`].
+    pub fn downsample(self, style: ContentStyle) -> ContentStyle {
+        ContentStyle {
+            foreground_color: style
+                .foreground_color
+                .and_then(|c| self.downsample_color(c)),
+            background_color: style
+                .background_color
+                .and_then(|c| self.downsample_color(c)),
+            underline_color: style.underline_color.and_then(|c| self.downsample_color(c)),
+            attributes: style.attributes,
+        }
+    }
+
+    /// Maps `color` to the nearest one this depth supports,
+    /// returning `None` for [`ColorDepth::NoColor`].
+    pub fn downsample_color(self, color: Color) -> Option<Color> {
+        match (self, color) {
+            (Self::NoColor, _) => None,
+            (Self::TrueColor, _) => Some(color),
+            (Self::Ansi256, Color::Rgb { r, g, b }) => {
+                Some(Color::AnsiValue(rgb_to_ansi256(r, g, b)))
+            }
+            (Self::Ansi16, Color::Rgb { r, g, b }) => Some(rgb_to_ansi16(r, g, b)),
+            (Self::Ansi16, Color::AnsiValue(value)) => {
+                let (r, g, b) = ansi256_to_rgb(value);
+                Some(rgb_to_ansi16(r, g, b))
+            }
+            _ => Some(color),
+        }
+    }
+}
+
+const COLOR_DEPTH_AUTO: u8 = u8::MAX;
+
+static COLOR_DEPTH: AtomicU8 = AtomicU8::new(COLOR_DEPTH_AUTO);
+
+/// Sets the color depth that [`crate::grapheme::StyledGraphemesDisplay`]
+/// downsamples colors to, overriding the detection from the environment.
+pub fn set_color_depth(depth: ColorDepth) {
+    COLOR_DEPTH.store(depth as u8, Ordering::Relaxed);
+}
+
+/// Returns the color depth colors are downsampled to,
+/// which is detected by [`ColorDepth::detect`] unless set with [`set_color_depth`].
+pub fn color_depth() -> ColorDepth {
+    match COLOR_DEPTH.load(Ordering::Relaxed) {
+        0 => ColorDepth::NoColor,
+        1 => ColorDepth::Ansi16,
+        2 => ColorDepth::Ansi256,
+        3 => ColorDepth::TrueColor,
+        _ => {
+            let depth = ColorDepth::detect();
+            set_color_depth(depth);
+            depth
+        }
+    }
+}
+
+/// The 16 standard colors with the RGB values of xterm.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels of each channel in the 6x6x6 color cube of the 256 colors.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn ansi256_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI16[value as usize].1,
+        16..=231 => {
+            let i = value - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (value - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - c as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * nearest_level(r) + 6 * nearest_level(g) + nearest_level(b);
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    [cube, grey]
+        .into_iter()
+        .min_by_key(|&value| distance((r, g, b), ansi256_to_rgb(value)))
+        .unwrap_or(cube)
+}
+
+fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::White)
+}
+
+#[cfg(test)]
+mod test {
+    mod parse {
+        use crate::{
+            crossterm::style::{Attribute, Attributes, Color},
+            style::{ParseStyleError, StyleBuilder},
+        };
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                StyleBuilder::new()
+                    .fgc(Color::Rgb {
+                        r: 0xff,
+                        g: 0x88,
+                        b: 0x00
+                    })
+                    .bgc(Color::Black)
+                    .ulc(Color::AnsiValue(208))
+                    .attrs(Attributes::from(Attribute::Bold) | Attribute::Underlined)
+                    .build(),
+                StyleBuilder::parse("bold fg:#ff8800 bg:black underline ul:208")
+                    .unwrap()
+                    .build(),
+            );
+        }
+
+        #[test]
+        fn test_with_empty_string() {
+            assert_eq!(
+                StyleBuilder::new().build(),
+                StyleBuilder::parse("").unwrap().build()
+            );
+        }
+
+        #[test]
+        fn test_with_unknown_color() {
+            assert_eq!(
+                Err(ParseStyleError::UnknownColor(String::from("blurple"))),
+                "bold fg:blurple".parse::<StyleBuilder>().map(|b| b.build()),
+            );
+            assert_eq!(
+                Err(ParseStyleError::UnknownColor(String::from("#ff88"))),
+                "bg:#ff88".parse::<StyleBuilder>().map(|b| b.build()),
+            );
+        }
+
+        #[test]
+        fn test_with_unknown_word() {
+            assert_eq!(
+                Err(ParseStyleError::UnknownWord(String::from("red"))),
+                "bold red".parse::<StyleBuilder>().map(|b| b.build()),
+            );
+        }
+    }
+
+    mod detect_from {
+        use std::collections::HashMap;
+
+        use crate::style::ColorDepth;
+
+        fn detect(vars: &[(&str, &str)]) -> ColorDepth {
+            let vars: HashMap<_, _> = vars.iter().copied().collect();
+            ColorDepth::detect_from(|key| vars.get(key).map(|v| v.to_string()))
+        }
+
+        #[test]
+        fn test() {
+            assert_eq!(ColorDepth::Ansi16, detect(&[("TERM", "xterm")]));
+            assert_eq!(ColorDepth::Ansi256, detect(&[("TERM", "xterm-256color")]));
+            assert_eq!(
+                ColorDepth::TrueColor,
+                detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")])
+            );
+            assert_eq!(ColorDepth::NoColor, detect(&[("TERM", "dumb")]));
+        }
+
+        #[test]
+        fn test_with_no_color() {
+            assert_eq!(
+                ColorDepth::NoColor,
+                detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")])
+            );
+            assert_eq!(
+                ColorDepth::TrueColor,
+                detect(&[("NO_COLOR", ""), ("COLORTERM", "truecolor")])
+            );
+        }
+    }
+
+    mod downsample {
+        use crate::{
+            crossterm::style::{Attribute, Attributes, Color},
+            style::{ColorDepth, StyleBuilder},
+        };
+
+        #[test]
+        fn test() {
+            let style = StyleBuilder::new()
+                .fgc(Color::Rgb {
+                    r: 0xff,
+                    g: 0x88,
+                    b: 0x00,
+                })
+                .bgc(Color::DarkCyan)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build();
+            assert_eq!(style, ColorDepth::TrueColor.downsample(style));
+            assert_eq!(
+                StyleBuilder::new()
+                    .fgc(Color::AnsiValue(208))
+                    .bgc(Color::DarkCyan)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                ColorDepth::Ansi256.downsample(style)
+            );
+            assert_eq!(
+                StyleBuilder::new()
+                    .fgc(Color::DarkYellow)
+                    .bgc(Color::DarkCyan)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                ColorDepth::Ansi16.downsample(style)
+            );
+            assert_eq!(
+                StyleBuilder::new()
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                ColorDepth::NoColor.downsample(style)
+            );
+        }
+
+        #[test]
+        fn test_with_grey() {
+            assert_eq!(
+                Some(Color::AnsiValue(244)),
+                ColorDepth::Ansi256.downsample_color(Color::Rgb {
+                    r: 0x80,
+                    g: 0x80,
+                    b: 0x80,
+                })
+            );
+            assert_eq!(
+                Some(Color::DarkGrey),
+                ColorDepth::Ansi16.downsample_color(Color::AnsiValue(244))
+            );
+        }
+    }
+}
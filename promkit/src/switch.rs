@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
+use crate::keyhint::KeyHint;
+
 #[derive(Clone)]
 pub struct ActiveKeySwitcher<S> {
     mapping: HashMap<String, S>,
+    hints: HashMap<String, Vec<KeyHint>>,
     active_key: String,
 }
 
@@ -11,6 +14,7 @@ impl<S> ActiveKeySwitcher<S> {
         let key = key.as_ref().to_string();
         Self {
             mapping: HashMap::new(),
+            hints: HashMap::new(),
             active_key: key.clone(),
         }
         .register(key, handler)
@@ -21,6 +25,31 @@ impl<S> ActiveKeySwitcher<S> {
         self
     }
 
+    /// Attaches key hints to the handler registered with the given key.
+    /// These are displayed while the handler is active.
+    pub fn register_hints<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.hints
+            .insert(key.as_ref().to_string(), hints.into_iter().collect());
+        self
+    }
+
+    /// Attaches key hints to the handler registered with the given key,
+    /// unless hints have been attached to it already.
+    pub fn register_hints_if_absent<K: AsRef<str>, I: IntoIterator<Item = KeyHint>>(
+        mut self,
+        key: K,
+        hints: I,
+    ) -> Self {
+        self.hints
+            .entry(key.as_ref().to_string())
+            .or_insert_with(|| hints.into_iter().collect());
+        self
+    }
+
     pub fn switch<K: AsRef<str>>(&mut self, key: K) {
         let key = key.as_ref().to_string();
         if self.mapping.contains_key(&key) {
@@ -35,4 +64,12 @@ impl<S> ActiveKeySwitcher<S> {
     pub fn get(&self) -> &S {
         self.mapping.get(&self.active_key).unwrap()
     }
+
+    /// Returns the key hints of the currently active handler.
+    pub fn hints(&self) -> &[KeyHint] {
+        self.hints
+            .get(&self.active_key)
+            .map(|hints| hints.as_slice())
+            .unwrap_or_default()
+    }
 }
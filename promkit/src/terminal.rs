@@ -1,8 +1,13 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use crate::{
     crossterm::{cursor, style, terminal},
+    locale,
     pane::Pane,
+    text_editor::write_osc52,
 };
 
 pub struct Terminal {
@@ -52,6 +57,12 @@ impl Terminal {
         })
     }
 
+    /// Sets the system clipboard to `text` with the OSC 52 escape sequence.
+    pub fn copy_to_clipboard(&mut self, text: &str) -> anyhow::Result<()> {
+        write_osc52(&mut io::stdout(), text)?;
+        Ok(())
+    }
+
     pub fn draw(&mut self, panes: &[Pane]) -> anyhow::Result<()> {
         let height = terminal::size()?.1;
 
@@ -64,7 +75,7 @@ impl Terminal {
             return crossterm::execute!(
                 io::stdout(),
                 terminal::Clear(terminal::ClearType::FromCursorDown),
-                style::Print("⚠️ Insufficient Space"),
+                style::Print(locale::messages().insufficient_space),
             )
             .map_err(anyhow::Error::from);
         }
@@ -107,3 +118,110 @@ impl Terminal {
         Ok(())
     }
 }
+
+static ACCESSIBLE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether prompts run in accessible mode, which is meant for screen readers.
+///
+/// In this mode the prompt area is not redrawn in place.
+/// Instead, [`PlainTerminal`] prints the question and then every line that changes,
+/// e.g. the focused item of a list, as plain text below the previous output.
+/// Key bindings and results are the same as in the normal mode.
+pub fn enable_accessible_mode(enabled: bool) {
+    ACCESSIBLE_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether prompts run in accessible mode.
+pub fn accessible_mode_enabled() -> bool {
+    ACCESSIBLE_MODE.load(Ordering::Relaxed)
+}
+
+/// A terminal for the accessible mode that writes panes as plain, unstyled lines,
+/// never moving the cursor back over previous output.
+#[derive(Default)]
+pub struct PlainTerminal {
+    /// The lines of each pane from the previous draw.
+    panes: Vec<Vec<String>>,
+}
+
+impl PlainTerminal {
+    /// Sets the system clipboard to `text` with the OSC 52 escape sequence,
+    /// as [`Terminal::copy_to_clipboard`] does.
+    pub fn copy_to_clipboard(&mut self, text: &str) -> anyhow::Result<()> {
+        write_osc52(&mut io::stdout(), text)?;
+        Ok(())
+    }
+
+    pub fn draw(&mut self, panes: &[Pane]) -> anyhow::Result<()> {
+        let panes = panes
+            .iter()
+            .map(|pane| {
+                pane.extract(pane.visible_row_count())
+                    .iter()
+                    .map(|row| row.to_string().trim_end().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (i, lines) in panes.iter().enumerate() {
+            let previous = self.panes.get(i).map(Vec::as_slice).unwrap_or_default();
+            for line in changed_lines(previous, lines) {
+                crossterm::queue!(io::stdout(), style::Print(line), style::Print("\r\n"))?;
+            }
+        }
+        io::stdout().flush()?;
+
+        self.panes = panes;
+        Ok(())
+    }
+}
+
+/// Returns the lines of `current` worth announcing compared with `previous`,
+/// skipping blank and unchanged lines, and lines that only lost a leading marker
+/// such as the cursor of a list moving away from an item.
+fn changed_lines<'a>(previous: &[String], current: &'a [String]) -> Vec<&'a str> {
+    current
+        .iter()
+        .map(|line| line.as_str())
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| {
+            !previous.iter().any(|prev| {
+                prev == line || (prev.len() > line.len() && prev.ends_with(line.trim_start()))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    mod changed_lines {
+        use crate::terminal::changed_lines;
+
+        fn lines(lines: &[&str]) -> Vec<String> {
+            lines.iter().map(|line| line.to_string()).collect()
+        }
+
+        #[test]
+        fn test() {
+            let previous = lines(&["❯ apple", "  banana", "  cherry"]);
+            let current = lines(&["  apple", "❯ banana", "  cherry"]);
+            assert_eq!(vec!["❯ banana"], changed_lines(&previous, &current));
+        }
+
+        #[test]
+        fn test_with_text_editor() {
+            let previous = lines(&["❯❯ ab"]);
+            assert_eq!(vec!["❯❯ a"], changed_lines(&previous, &lines(&["❯❯ a"])));
+            assert_eq!(vec!["❯❯ b"], changed_lines(&previous, &lines(&["❯❯ b"])));
+        }
+
+        #[test]
+        fn test_with_first_draw() {
+            let current = lines(&["Which fruit?", "", "❯ apple"]);
+            assert_eq!(
+                vec!["Which fruit?", "❯ apple"],
+                changed_lines(&[], &current)
+            );
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::{fs, path::Path, sync::RwLock};
+
+use anyhow::Context;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    style::StyleBuilder,
+};
+
+/// A set of styles shared by all presets in [`crate::preset`].
+///
+/// Presets read the process-wide default theme (see [`set_default_theme`])
+/// when they are constructed, and the individual style setters of each preset
+/// can still be used to override a part of it afterwards.
+///
+/// A theme can also be loaded from a TOML or JSON file (see [`Theme::load`]),
+/// where each style is written as a style string (see [`StyleBuilder::parse`])
+/// and omitted styles are taken from [`Theme::default`]:
+///
+/// ```toml
+/// title_style = "bold"
+/// prefix_style = "fg:#ff8800"
+/// active_item_style = "bold fg:black bg:dark_cyan"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// Style for titles.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub title_style: ContentStyle,
+    /// Style for the prompt string displayed before the input text.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub prefix_style: ContentStyle,
+    /// Style for the character under the cursor of text editors.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub active_char_style: ContentStyle,
+    /// Style for the other characters of text editors.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub inactive_char_style: ContentStyle,
+    /// Style for the selected characters of text editors.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub selection_style: ContentStyle,
+    /// Style for the line numbers of multi-line text editors.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub line_number_style: ContentStyle,
+    /// Style for the selected item of lists, checkboxes and trees.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub active_item_style: ContentStyle,
+    /// Style for unselected items of lists, checkboxes and trees.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub inactive_item_style: ContentStyle,
+    /// Style for the selected suggestion of readline.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub active_suggestion_style: ContentStyle,
+    /// Style for unselected suggestions of readline.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub inactive_suggestion_style: ContentStyle,
+    /// Style for error messages, e.g. from validators.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub error_message_style: ContentStyle,
+    /// Style for keys in the footer and the help overlay.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub hint_key_style: ContentStyle,
+    /// Style for descriptions in the footer and the help overlay.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub hint_description_style: ContentStyle,
+
+    /// Style for `{}` in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_curly_brackets_style: ContentStyle,
+    /// Style for `[]` in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_square_brackets_style: ContentStyle,
+    /// Style for keys in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_key_style: ContentStyle,
+    /// Style for string values in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_string_value_style: ContentStyle,
+    /// Style for number values in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_number_value_style: ContentStyle,
+    /// Style for boolean values in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_boolean_value_style: ContentStyle,
+    /// Style for null values in JSON.
+    #[serde(deserialize_with = "deserialize_style")]
+    pub json_null_value_style: ContentStyle,
+}
+
+impl Default for Theme {
+    /// The theme promkit has always used.
+    fn default() -> Self {
+        let bold = StyleBuilder::new()
+            .attrs(Attributes::from(Attribute::Bold))
+            .build();
+        Self {
+            title_style: bold,
+            prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+            active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
+            inactive_char_style: StyleBuilder::new().build(),
+            selection_style: StyleBuilder::new().bgc(Color::DarkGrey).build(),
+            line_number_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+            active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
+            inactive_item_style: StyleBuilder::new().build(),
+            active_suggestion_style: StyleBuilder::new()
+                .fgc(Color::DarkGrey)
+                .bgc(Color::DarkYellow)
+                .build(),
+            inactive_suggestion_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+            error_message_style: StyleBuilder::new()
+                .fgc(Color::DarkRed)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build(),
+            hint_key_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
+            hint_description_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+            json_curly_brackets_style: bold,
+            json_square_brackets_style: bold,
+            json_key_style: StyleBuilder::new().fgc(Color::DarkBlue).build(),
+            json_string_value_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+            json_number_value_style: StyleBuilder::new().build(),
+            json_boolean_value_style: StyleBuilder::new().build(),
+            json_null_value_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme without colors, relying on text attributes only.
+    pub fn monochrome() -> Self {
+        let plain = StyleBuilder::new().build();
+        let bold = StyleBuilder::new()
+            .attrs(Attributes::from(Attribute::Bold))
+            .build();
+        let reverse = StyleBuilder::new()
+            .attrs(Attributes::from(Attribute::Reverse))
+            .build();
+        let dim = StyleBuilder::new()
+            .attrs(Attributes::from(Attribute::Dim))
+            .build();
+        Self {
+            title_style: bold,
+            prefix_style: bold,
+            active_char_style: reverse,
+            inactive_char_style: plain,
+            selection_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Underlined))
+                .build(),
+            line_number_style: dim,
+            active_item_style: reverse,
+            inactive_item_style: plain,
+            active_suggestion_style: reverse,
+            inactive_suggestion_style: dim,
+            error_message_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold) | Attribute::Underlined)
+                .build(),
+            hint_key_style: bold,
+            hint_description_style: dim,
+            json_curly_brackets_style: bold,
+            json_square_brackets_style: bold,
+            json_key_style: bold,
+            json_string_value_style: plain,
+            json_number_value_style: plain,
+            json_boolean_value_style: plain,
+            json_null_value_style: dim,
+        }
+    }
+
+    /// A theme with bright colors for low-contrast displays.
+    pub fn high_contrast() -> Self {
+        let bold = |color: Color| {
+            StyleBuilder::new()
+                .fgc(color)
+                .attrs(Attributes::from(Attribute::Bold))
+                .build()
+        };
+        Self {
+            title_style: bold(Color::White),
+            prefix_style: bold(Color::Green),
+            active_char_style: StyleBuilder::new()
+                .fgc(Color::Black)
+                .bgc(Color::Yellow)
+                .build(),
+            inactive_char_style: StyleBuilder::new().fgc(Color::White).build(),
+            selection_style: StyleBuilder::new()
+                .fgc(Color::Black)
+                .bgc(Color::White)
+                .build(),
+            line_number_style: StyleBuilder::new().fgc(Color::White).build(),
+            active_item_style: StyleBuilder::new()
+                .fgc(Color::Black)
+                .bgc(Color::Cyan)
+                .build(),
+            inactive_item_style: StyleBuilder::new().fgc(Color::White).build(),
+            active_suggestion_style: StyleBuilder::new()
+                .fgc(Color::Black)
+                .bgc(Color::Yellow)
+                .build(),
+            inactive_suggestion_style: StyleBuilder::new().fgc(Color::Grey).build(),
+            error_message_style: bold(Color::Red),
+            hint_key_style: bold(Color::Cyan),
+            hint_description_style: StyleBuilder::new().fgc(Color::Grey).build(),
+            json_curly_brackets_style: bold(Color::White),
+            json_square_brackets_style: bold(Color::White),
+            json_key_style: StyleBuilder::new().fgc(Color::Cyan).build(),
+            json_string_value_style: StyleBuilder::new().fgc(Color::Green).build(),
+            json_number_value_style: StyleBuilder::new().fgc(Color::Magenta).build(),
+            json_boolean_value_style: StyleBuilder::new().fgc(Color::Yellow).build(),
+            json_null_value_style: StyleBuilder::new().fgc(Color::Grey).build(),
+        }
+    }
+
+    /// Parses a theme written in TOML.
+    pub fn from_toml(s: &str) -> anyhow::Result<Self> {
+        toml::from_str(s).context("failed to parse theme as TOML")
+    }
+
+    /// Parses a theme written in JSON.
+    pub fn from_json(s: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(s).context("failed to parse theme as JSON")
+    }
+
+    /// Loads a theme from a file,
+    /// which is read as JSON if its extension is `.json` and as TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read theme from {}", path.display()))?;
+        let theme = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        };
+        theme.with_context(|| format!("invalid theme in {}", path.display()))
+    }
+}
+
+fn deserialize_style<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ContentStyle, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    StyleBuilder::parse(&s)
+        .map(|builder| builder.build())
+        .map_err(serde::de::Error::custom)
+}
+
+static DEFAULT_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// Sets the theme picked up by all presets constructed from now on.
+pub fn set_default_theme(theme: Theme) {
+    *DEFAULT_THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(theme);
+}
+
+/// Returns the theme picked up by presets,
+/// which is [`Theme::default`] unless changed with [`set_default_theme`].
+pub fn default_theme() -> Theme {
+    DEFAULT_THEME
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    mod from_toml {
+        use crate::{crossterm::style::Color, style::StyleBuilder, theme::Theme};
+
+        #[test]
+        fn test() {
+            let theme = Theme::from_toml(
+                r#"
+                prefix_style = "bold fg:#ff8800"
+                active_item_style = "fg:black bg:dark_cyan"
+                "#,
+            )
+            .unwrap();
+            assert_eq!(
+                StyleBuilder::parse("bold fg:#ff8800").unwrap().build(),
+                theme.prefix_style
+            );
+            assert_eq!(Some(Color::Black), theme.active_item_style.foreground_color);
+            assert_eq!(Theme::default().title_style, theme.title_style);
+        }
+
+        #[test]
+        fn test_with_unknown_color() {
+            let err = Theme::from_toml(r#"prefix_style = "fg:blurple""#).unwrap_err();
+            assert!(format!("{:#}", err).contains("unknown color `blurple`"));
+        }
+
+        #[test]
+        fn test_with_unknown_field() {
+            assert!(Theme::from_toml(r#"prefix = "bold""#).is_err());
+        }
+    }
+
+    mod from_json {
+        use crate::{crossterm::style::Color, theme::Theme};
+
+        #[test]
+        fn test() {
+            let theme = Theme::from_json(r#"{"json_key_style": "fg:blue"}"#).unwrap();
+            assert_eq!(Some(Color::Blue), theme.json_key_style.foreground_color);
+        }
+    }
+
+    mod monochrome {
+        use crate::theme::Theme;
+
+        #[test]
+        fn test() {
+            let theme = Theme::monochrome();
+            for style in [
+                theme.title_style,
+                theme.prefix_style,
+                theme.active_char_style,
+                theme.active_item_style,
+                theme.active_suggestion_style,
+                theme.error_message_style,
+                theme.hint_key_style,
+                theme.json_key_style,
+                theme.json_null_value_style,
+            ] {
+                assert_eq!(None, style.foreground_color);
+                assert_eq!(None, style.background_color);
+            }
+        }
+    }
+}
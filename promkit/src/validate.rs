@@ -1,5 +1,9 @@
-pub type Validator<T> = fn(&T) -> bool;
-pub type ErrorMessageGenerator<T> = fn(&T) -> String;
+/// Checks whether an input is valid.
+/// It can be a closure, e.g. one checking against a list loaded at runtime.
+pub type Validator<T> = Box<dyn Fn(&T) -> bool>;
+/// Generates an error message for an invalid input.
+/// The message may contain markup tags (see [`crate::markup`]) if markup is enabled on the preset.
+pub type ErrorMessageGenerator<T> = Box<dyn Fn(&T) -> String>;
 
 /// A generic structure for validating inputs of any type.
 ///
@@ -25,19 +29,23 @@ impl<T: ?Sized> ValidatorManager<T> {
     /// # Arguments
     ///
     /// * `validator` - A function that takes a reference
-    /// to an input of type `T` and returns a boolean
-    /// indicating whether the input passes the validation.
+    ///   to an input of type `T` and returns a boolean
+    ///   indicating whether the input passes the validation.
     /// * `error_message_generator` - A function that takes a reference
-    /// to an input of type `T` and returns a `String`
-    /// that describes the validation error.
+    ///   to an input of type `T` and returns a `String`
+    ///   that describes the validation error.
     ///
     /// # Returns
     ///
     /// Returns a new instance of `Validator<T>`.
-    pub fn new(validator: Validator<T>, error_message_generator: ErrorMessageGenerator<T>) -> Self {
+    pub fn new<V, E>(validator: V, error_message_generator: E) -> Self
+    where
+        V: Fn(&T) -> bool + 'static,
+        E: Fn(&T) -> String + 'static,
+    {
         Self {
-            validator,
-            error_message_generator,
+            validator: Box::new(validator),
+            error_message_generator: Box::new(error_message_generator),
         }
     }
 
@@ -47,7 +55,7 @@ impl<T: ?Sized> ValidatorManager<T> {
     /// # Arguments
     ///
     /// * `input` - A reference
-    /// to the input of type `T` to be validated.
+    ///   to the input of type `T` to be validated.
     ///
     /// # Returns
     ///
@@ -63,7 +71,7 @@ impl<T: ?Sized> ValidatorManager<T> {
     /// # Arguments
     ///
     /// * `input` - A reference to the input of type `T`
-    /// for which to generate an error message.
+    ///   for which to generate an error message.
     ///
     /// # Returns
     ///
@@ -72,3 +80,25 @@ impl<T: ?Sized> ValidatorManager<T> {
         (self.error_message_generator)(input)
     }
 }
+
+#[cfg(test)]
+mod test {
+    mod validate {
+        use crate::validate::ValidatorManager;
+
+        #[test]
+        fn test_with_closure() {
+            let known: Vec<String> = ["main", "develop"].map(String::from).to_vec();
+            let validator = ValidatorManager::<str>::new(
+                move |text| known.iter().any(|name| name == text),
+                |text| format!("unknown branch `{}`", text),
+            );
+            assert!(validator.validate("main"));
+            assert!(!validator.validate("feature"));
+            assert_eq!(
+                "unknown branch `feature`",
+                validator.generate_error_message("feature")
+            );
+        }
+    }
+}